@@ -0,0 +1,32 @@
+//! solving many instances at once.
+use rand::SeedableRng;
+use rayon::prelude::*;
+
+use crate::{Solution, StOBGA, SteinerProblem, StobgaConfig};
+
+/// solves every problem once per seed, in parallel, and returns the best
+/// solution of each problem in the order of the problems. Every run uses its
+/// own random generator seeded with one of the seeds, so the results are
+/// reproducible. Without any seeds, every problem is solved with seed 0.
+pub fn solve_batch(
+    problems: Vec<SteinerProblem>,
+    config: &StobgaConfig,
+    seeds: &[u64],
+) -> Vec<Solution> {
+    let seeds = if seeds.is_empty() { &[0] } else { seeds };
+    problems
+        .into_par_iter()
+        .map(|problem| {
+            seeds
+                .par_iter()
+                .map(|&seed| {
+                    let rng = rand_pcg::Pcg32::seed_from_u64(seed);
+                    let mut stobga = StOBGA::with_config(rng, problem.clone(), config.clone());
+                    stobga.run();
+                    stobga.best_solution()
+                })
+                .min_by(|s1, s2| s1.total_weight.total_cmp(&s2.total_weight))
+                .unwrap()
+        })
+        .collect()
+}
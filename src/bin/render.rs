@@ -0,0 +1,74 @@
+//! solves a single instance and writes the best tree as SVG and as GeoJSON.
+//!
+//!     render [<terminal file> <obstacle file>] [<output prefix>]
+//!
+//! Without instance files a small bundled instance is solved. The drawings
+//! go to `<output prefix>.svg` and `<output prefix>.geojson`, `render` by
+//! default.
+use std::fs::File;
+use std::io::BufReader;
+
+use stobga::{StOBGA, StobgaConfig, SteinerProblem};
+
+/// four terminals around a solid block with a cheap passage next to it.
+const TERMINALS: &str = "x,y
+1,1
+9,1
+1,9
+9,9
+";
+
+const OBSTACLES: &str = "max
+4,4
+6,4
+6,6
+4,6
+
+0.5
+6,4
+8,4
+8,6
+6,6";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (problem, prefix) = match args.as_slice() {
+        [terminals, obstacles, rest @ ..] => {
+            let open = |path: &String| {
+                BufReader::new(
+                    File::open(path).unwrap_or_else(|_| panic!("could not open {}", path)),
+                )
+            };
+            (
+                SteinerProblem::from_readers(open(terminals), open(obstacles)),
+                rest.first().cloned(),
+            )
+        }
+        [prefix] => (
+            SteinerProblem::from_readers(TERMINALS.as_bytes(), OBSTACLES.as_bytes()),
+            Some(prefix.clone()),
+        ),
+        [] => (
+            SteinerProblem::from_readers(TERMINALS.as_bytes(), OBSTACLES.as_bytes()),
+            None,
+        ),
+    };
+    let problem = problem.unwrap_or_else(|error| {
+        eprintln!("invalid instance: {}", error);
+        std::process::exit(1);
+    });
+    let prefix = prefix.unwrap_or_else(|| "render".to_string());
+
+    let mut stobga = StOBGA::<rand_pcg::Pcg32>::from_seed(0, problem, StobgaConfig::default());
+    stobga.run();
+    let solution = stobga.best_solution();
+
+    let svg_path = format!("{}.svg", prefix);
+    let geojson_path = format!("{}.geojson", prefix);
+    std::fs::write(&svg_path, stobga.instance_to_svg(0))
+        .unwrap_or_else(|_| panic!("could not write {}", svg_path));
+    std::fs::write(&geojson_path, solution.to_geojson(stobga.problem()))
+        .unwrap_or_else(|_| panic!("could not write {}", geojson_path));
+    eprintln!("{}", stobga.summary());
+    println!("{}\n{}", svg_path, geojson_path);
+}
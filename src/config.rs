@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use crate::crossover::{Crossover, XSplit};
+use crate::fitness::{Fitness, TotalWeight};
+use crate::util::SteinerRegion;
+use crate::{
+    geometry::{Bounds, RADIANS_120_DEGREE},
+    Point, SolverError, SteinerProblem, EPSILON, POPULATION_SIZE, RECESSION_DURATION,
+};
+
+/// how the t2 individuals of the initial population place their Steiner
+/// points, or the t3 individuals pick their obstacle corners.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InitStrategy {
+    /// uniformly at random within the bounds
+    Random,
+    /// at the centers of an `nx` by `ny` grid of cells over the bounds,
+    /// except for cells centered in a solid obstacle
+    Grid { nx: usize, ny: usize },
+    /// the first individual gets the centers of `k` clusters of the
+    /// terminals (except those inside a solid obstacle), the others are
+    /// seeded at random
+    Kmeans { k: usize },
+    /// the t2 individuals are seeded at random, the t3 individuals include
+    /// each corner with a probability falling with its distance to the
+    /// closest terminal, `exp(-distance / average terminal distance)`,
+    /// instead of a uniformly drawn subset
+    CornersNearTerminals,
+}
+
+/// how the offspring of a generation enter the population.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplacementStrategy {
+    /// a third of the population is replaced by offspring each generation,
+    /// the individuals to die are chosen by tournaments
+    Generational,
+    /// a single recombination per generation, whose children replace the
+    /// worst individuals
+    SteadyState,
+}
+
+/// the parameters of a run of [StOBGA](crate::StOBGA). The defaults follow
+/// the original paper.
+#[derive(Clone)]
+pub struct StobgaConfig {
+    /// the amount of individuals in the population
+    pub population_size: usize,
+    /// the amount of initial individuals using the Delaunay centroids as
+    /// Steiner points
+    pub t1: usize,
+    /// the amount of initial individuals with random Steiner points and all
+    /// obstacle corners
+    pub t2: usize,
+    /// the amount of initial individuals with a random subset of the
+    /// obstacle corners
+    pub t3: usize,
+    /// the shares of the population for t1, t2 and t3, replacing the counts
+    /// if given. Set through [StobgaConfig::init_fractions].
+    pub init_fractions: Option<(f32, f32, f32)>,
+    /// lets individuals include obstacle corners as Steiner points. Without,
+    /// the t2 individuals get no corners, the t3 individuals are empty and
+    /// flip-move never toggles a corner.
+    pub use_obstacle_corners: bool,
+    /// how the t2 individuals get their Steiner points
+    pub init_strategy: InitStrategy,
+    /// builds the t2 and t3 individuals and their trees on all cores. The
+    /// initial population is the same either way.
+    pub parallel_init: bool,
+    /// the amount of individuals competing in each tournament selection
+    pub tournament_size: usize,
+    /// the amount of generations without improvement after which a run ends
+    pub recession_duration: usize,
+    /// ends a run after this many generations, regardless of improvements
+    pub max_generations: Option<usize>,
+    /// the angle (in radians) below which two edges of a node are
+    /// considered for a new Steiner point in their Fermat point
+    pub add_steiner_angle_threshold: f32,
+    /// moves Steiner points leaving the convex hull of the terminals back
+    /// onto it. Without obstacles, optimal Steiner points lie in the hull.
+    pub hull_constraint: bool,
+    /// a polygon the Steiner points have to lie in, e.g. a property
+    /// boundary. Sampled and mutated points outside of it are rejected.
+    pub steiner_region_polygon: Option<Vec<Point>>,
+    /// boxes no Steiner point may be placed in, e.g. for lack of space for
+    /// a junction. Unlike obstacles they do not affect the edges, which
+    /// cross them freely.
+    pub steiner_keepout: Vec<Bounds>,
+    /// makes crossing a solid obstacle cost this much per length instead of
+    /// making the edge infinitely expensive. Solutions still report whether
+    /// they cross a solid obstacle.
+    pub solid_penalty: Option<f32>,
+    /// the margin edges should keep from obstacle boundaries. An edge
+    /// coming closer than this costs the difference on top of its length.
+    pub clearance: f32,
+    /// the longest an edge may be, e.g. a physical span limit. Longer
+    /// edges cost `INF`, or `max_edge_length_penalty` per length above the
+    /// limit on top of their cost if given.
+    pub max_edge_length: Option<f32>,
+    pub max_edge_length_penalty: Option<f32>,
+    /// the operator recombining two parents into two children
+    pub crossover: Arc<dyn Crossover + Send + Sync>,
+    /// the objective tournaments and the ranking of the population go by.
+    /// Reports and summaries still state the total weight.
+    pub fitness: Arc<dyn Fitness + Send + Sync>,
+    /// moves Steiner points of children that lie in a solid obstacle (e.g.
+    /// inherited from imported individuals) to a random free spot, or drops
+    /// them if none is found
+    pub repair_children: bool,
+    /// keeps only the lighter of the two children of each crossover
+    pub single_child: bool,
+    /// replaces the worst quarter of the population with random immigrants
+    /// whenever the [diversity](crate::StOBGA::diversity) drops below this
+    pub diversity_threshold: Option<f32>,
+    /// picks the mutation operator by probabilities following the recent
+    /// improvement rates of the operators instead of the fixed schedule
+    /// favoring flip-move. No probability drops below this floor (at most
+    /// a third).
+    pub adaptive_mutation: Option<f32>,
+    /// how offspring replace the individuals of the population
+    pub replacement: ReplacementStrategy,
+}
+
+impl Default for StobgaConfig {
+    fn default() -> Self {
+        Self {
+            population_size: POPULATION_SIZE,
+            t1: 1,
+            t2: 50,
+            t3: 50,
+            init_fractions: None,
+            use_obstacle_corners: true,
+            init_strategy: InitStrategy::Random,
+            parallel_init: false,
+            tournament_size: 5,
+            recession_duration: RECESSION_DURATION,
+            max_generations: None,
+            add_steiner_angle_threshold: RADIANS_120_DEGREE,
+            hull_constraint: false,
+            steiner_region_polygon: None,
+            steiner_keepout: Vec::new(),
+            solid_penalty: None,
+            clearance: 0.0,
+            max_edge_length: None,
+            max_edge_length_penalty: None,
+            crossover: Arc::new(XSplit),
+            fitness: Arc::new(TotalWeight),
+            repair_children: false,
+            single_child: false,
+            diversity_threshold: None,
+            adaptive_mutation: None,
+            replacement: ReplacementStrategy::Generational,
+        }
+    }
+}
+
+impl StobgaConfig {
+    /// sizes t1, t2 and t3 as fractions of the population size instead of
+    /// counts, e.g. `(0.002, 0.1, 0.1)` for the defaults. The rest of the
+    /// population is still filled through crossover, so the fractions may
+    /// sum up to at most 1.
+    pub fn init_fractions(mut self, f1: f32, f2: f32, f3: f32) -> Self {
+        self.init_fractions = Some((f1, f2, f3));
+        self
+    }
+
+    /// checks the settings [StOBGA::try_with_config](crate::StOBGA::try_with_config)
+    /// cannot work with.
+    pub(crate) fn validate(&self) -> Result<(), SolverError> {
+        if !(1..=self.population_size).contains(&self.tournament_size) {
+            return Err(SolverError::TournamentSize {
+                size: self.tournament_size,
+                population_size: self.population_size,
+            });
+        }
+        Ok(())
+    }
+
+    /// the amount of t1, t2 and t3 individuals, from the fractions if given.
+    /// The fractions are rounded by largest remainder, so the counts add up
+    /// to the rounded total and never exceed the population size.
+    pub(crate) fn init_counts(&self) -> Result<(usize, usize, usize), SolverError> {
+        let Some((f1, f2, f3)) = self.init_fractions else {
+            return Ok((self.t1, self.t2, self.t3));
+        };
+        let fractions = [f1, f2, f3];
+        if !fractions.iter().all(|&f| f >= 0.0) || f1 + f2 + f3 > 1.0 + EPSILON {
+            return Err(SolverError::InitFractions { fractions: (f1, f2, f3) });
+        }
+        let n = self.population_size;
+        let exact = fractions.map(|f| f * n as f32);
+        let mut counts = exact.map(|e| e.floor() as usize);
+        let total = (((f1 + f2 + f3) * n as f32).round() as usize).min(n);
+        let mut by_remainder = [0, 1, 2];
+        by_remainder.sort_by(|&i, &j| {
+            (exact[j] - counts[j] as f32).total_cmp(&(exact[i] - counts[i] as f32))
+        });
+        let missing = total.saturating_sub(counts.iter().sum());
+        for &i in by_remainder.iter().take(missing) {
+            counts[i] += 1;
+        }
+        Ok((counts[0], counts[1], counts[2]))
+    }
+
+    /// the Steiner region polygon together with the keep-out boxes.
+    pub(crate) fn steiner_region(&self) -> SteinerRegion<'_> {
+        SteinerRegion {
+            polygon: self.steiner_region_polygon.as_deref(),
+            keepout: &self.steiner_keepout,
+        }
+    }
+
+    /// the amount of obstacle corners of `problem` individuals may include.
+    pub(crate) fn corner_count(&self, problem: &SteinerProblem) -> usize {
+        if self.use_obstacle_corners {
+            problem.obstacle_corners.len()
+        } else {
+            0
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use indexmap::IndexSet;
 
-#[derive(Clone)]
+#[derive(Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Corners {
     pub included : IndexSet<usize>
 }
@@ -0,0 +1,124 @@
+//! recombination operators. The one used by a run is chosen through
+//! [StobgaConfig::crossover](crate::StobgaConfig::crossover).
+
+use indexmap::IndexSet;
+use rand::{Rng, RngCore};
+
+use crate::corners::Corners;
+use crate::{Chromosome, OPoint, Pt, SteinerProblem};
+
+pub trait Crossover {
+    /// two children recombined from the parents `p1` and `p2`.
+    fn recombine(
+        &self,
+        p1: &Chromosome,
+        p2: &Chromosome,
+        problem: &SteinerProblem,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome);
+}
+
+/// splits both parents at a random x coordinate; the first child gets the
+/// left part of `p1` and the right part of `p2`, the second child the rest.
+/// This is the crossover of the original paper.
+pub struct XSplit;
+
+/// hands every gene of both parents to a random one of the two children.
+pub struct Uniform;
+
+/// swaps the genes between two random x coordinates between the parents.
+pub struct TwoPoint;
+
+/// distributes the genes of both parents, asking `to_first` for each gene
+/// (given its x coordinate and whether it comes from `p1`) whether it goes
+/// to the first child.
+fn distribute(
+    p1: &Chromosome,
+    p2: &Chromosome,
+    problem: &SteinerProblem,
+    mut to_first: impl FnMut(f32, bool) -> bool,
+) -> (Chromosome, Chromosome) {
+    let mut steiner_points_1: IndexSet<OPoint> = IndexSet::new();
+    let mut steiner_points_2 = IndexSet::new();
+    let mut obstacle_corners_1 = Corners::new();
+    let mut obstacle_corners_2 = Corners::new();
+    for (parent, from_first) in [(p1, true), (p2, false)] {
+        for point in parent.steiner_points.iter() {
+            if to_first(Pt::from(*point).x, from_first) {
+                steiner_points_1.insert(*point);
+            } else {
+                steiner_points_2.insert(*point);
+            }
+        }
+        for index in parent.included_corners.iter() {
+            if to_first(Pt::from(problem.obstacle_corners[index]).x, from_first) {
+                obstacle_corners_1.insert(index);
+            } else {
+                obstacle_corners_2.insert(index);
+            }
+        }
+    }
+    (
+        Chromosome {
+            steiner_points: steiner_points_1,
+            included_corners: obstacle_corners_1,
+            ..Default::default()
+        },
+        Chromosome {
+            steiner_points: steiner_points_2,
+            included_corners: obstacle_corners_2,
+            ..Default::default()
+        },
+    )
+}
+
+impl Crossover for XSplit {
+    fn recombine(
+        &self,
+        p1: &Chromosome,
+        p2: &Chromosome,
+        problem: &SteinerProblem,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        let bounds = problem.sampling_bounds();
+        let random_x_value = rng.gen_range(bounds.min_x..bounds.max_x);
+        distribute(p1, p2, problem, |x, from_first| {
+            if from_first {
+                x < random_x_value
+            } else {
+                x > random_x_value
+            }
+        })
+    }
+}
+
+impl Crossover for Uniform {
+    fn recombine(
+        &self,
+        p1: &Chromosome,
+        p2: &Chromosome,
+        problem: &SteinerProblem,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        distribute(p1, p2, problem, |_, _| rng.gen_bool(0.5))
+    }
+}
+
+impl Crossover for TwoPoint {
+    fn recombine(
+        &self,
+        p1: &Chromosome,
+        p2: &Chromosome,
+        problem: &SteinerProblem,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        let bounds = problem.sampling_bounds();
+        let x1 = rng.gen_range(bounds.min_x..bounds.max_x);
+        let x2 = rng.gen_range(bounds.min_x..bounds.max_x);
+        let (low, high) = (x1.min(x2), x1.max(x2));
+        distribute(p1, p2, problem, |x, from_first| {
+            let swapped = low <= x && x < high;
+            from_first != swapped
+        })
+    }
+}
@@ -0,0 +1,51 @@
+//! the errors of setting up a problem or a solver.
+
+/// why a [SteinerProblem](crate::SteinerProblem) or a [StOBGA](crate::StOBGA)
+/// could not be set up.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolverError {
+    /// a terminal or obstacle corner with a `NaN` or infinite coordinate
+    NonFiniteCoordinate { x: f32, y: f32 },
+    /// an obstacle, by index, whose weight is not positive. Weights below 1
+    /// make fast corridors, but a weight of 0 or less would make crossing
+    /// the obstacle free or even pay off.
+    NonPositiveWeight { obstacle: usize, weight: f32 },
+    /// an obstacle, by index, with a negative entry cost
+    NegativeEntryCost { obstacle: usize, entry_cost: f32 },
+    /// a tournament size of 0 or above the population size
+    TournamentSize { size: usize, population_size: usize },
+    /// initialization fractions that are negative or sum up to more than 1
+    InitFractions { fractions: (f32, f32, f32) },
+}
+
+impl std::fmt::Display for SolverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverError::NonFiniteCoordinate { x, y } => {
+                write!(f, "the coordinate ({}, {}) is not finite", x, y)
+            }
+            SolverError::NonPositiveWeight { obstacle, weight } => {
+                write!(f, "obstacle {} has the weight {}, which is not positive", obstacle, weight)
+            }
+            SolverError::NegativeEntryCost { obstacle, entry_cost } => {
+                write!(f, "obstacle {} has the negative entry cost {}", obstacle, entry_cost)
+            }
+            SolverError::TournamentSize { size, population_size } => {
+                write!(
+                    f,
+                    "the tournament size {} is not between 1 and the population size {}",
+                    size, population_size
+                )
+            }
+            SolverError::InitFractions { fractions } => {
+                write!(
+                    f,
+                    "the initialization fractions {:?} are negative or sum up to more than 1",
+                    fractions
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
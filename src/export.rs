@@ -0,0 +1,60 @@
+//! writing solutions in formats other tools understand.
+use serde_json::{json, Value};
+
+use crate::{Point, Solution, SteinerProblem, INF};
+
+impl Solution {
+    /// the solution as a GeoJSON feature collection. Every feature has a
+    /// `kind` property: `terminal` (with its `label`), `steiner`, `corner`,
+    /// `edge` or `obstacle` (with its `name` and its `weight`, which is
+    /// `null` for solid obstacles).
+    pub fn to_geojson(&self, problem: &SteinerProblem) -> String {
+        let point = |p: Point| json!({"type": "Point", "coordinates": [p.0, p.1]});
+        let mut features = Vec::new();
+        for obstacle in &problem.obstacles {
+            let mut ring = obstacle
+                .points
+                .iter()
+                .map(|p| json!([p.0, p.1]))
+                .collect::<Vec<_>>();
+            if let Some(first) = ring.first().cloned() {
+                ring.push(first);
+            }
+            let weight = if obstacle.weight == INF {
+                Value::Null
+            } else {
+                json!(obstacle.weight)
+            };
+            features.push(feature(
+                json!({"type": "Polygon", "coordinates": [ring]}),
+                json!({"kind": "obstacle", "weight": weight, "name": obstacle.name}),
+            ));
+        }
+        for &(from, to) in &self.edges {
+            features.push(feature(
+                json!({"type": "LineString", "coordinates": [[from.0, from.1], [to.0, to.1]]}),
+                json!({"kind": "edge"}),
+            ));
+        }
+        for (&terminal, label) in problem.terminals.iter().zip(&problem.terminal_labels) {
+            features.push(feature(
+                point(terminal),
+                json!({"kind": "terminal", "label": label}),
+            ));
+        }
+        for &steiner_point in &self.steiner_points {
+            features.push(feature(point(steiner_point), json!({"kind": "steiner"})));
+        }
+        for &corner in &self.included_corners {
+            features.push(feature(
+                point(problem.obstacle_corners[corner]),
+                json!({"kind": "corner"}),
+            ));
+        }
+        json!({"type": "FeatureCollection", "features": features}).to_string()
+    }
+}
+
+fn feature(geometry: Value, properties: Value) -> Value {
+    json!({"type": "Feature", "geometry": geometry, "properties": properties})
+}
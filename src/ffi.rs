@@ -0,0 +1,159 @@
+//! a C interface for embedding StOBGA.
+//!
+//! A problem is set up with [stobga_new], which returns an opaque handle
+//! owned by the caller. The handle is solved with [stobga_run], the edges of
+//! the best tree are copied out with [stobga_edges] and the handle must
+//! eventually be released with [stobga_free]. All functions accept null
+//! handles and then do nothing. Panics do not cross the interface, they
+//! are reported like invalid arguments.
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use petgraph::visit::EdgeRef;
+use rand::SeedableRng;
+
+use crate::{Obstacle, StOBGA, SteinerProblem, INF, POPULATION_SIZE};
+
+/// an opaque handle to a set up instance of the algorithm.
+pub struct StobgaHandle {
+    stobga: StOBGA<rand_pcg::Pcg32>,
+}
+
+/// sets up the algorithm for a problem given as flat arrays.
+///
+/// `terminals` holds `terminal_count` pairs of x and y coordinates.
+/// `obstacle_vertices` holds the corners of all `obstacle_count` obstacles
+/// as consecutive x and y pairs, where the i-th obstacle takes up
+/// `vertex_counts[i]` pairs and has the weight `weights[i]`. A weight of
+/// `INFINITY` marks a solid obstacle. The arrays are only read during this
+/// call and remain owned by the caller.
+///
+/// Returns null if there are less than two terminals, a required array is
+/// null, an obstacle has less than three vertices, a coordinate is not
+/// finite or a weight is not positive (or `NaN`). Otherwise the returned handle must be
+/// released with [stobga_free].
+///
+/// # Safety
+/// Every non-null pointer must point to at least as many readable values as
+/// described by the counts.
+#[no_mangle]
+pub unsafe extern "C" fn stobga_new(
+    terminals: *const f32,
+    terminal_count: usize,
+    obstacle_vertices: *const f32,
+    vertex_counts: *const usize,
+    weights: *const f32,
+    obstacle_count: usize,
+    seed: u64,
+) -> *mut StobgaHandle {
+    if terminals.is_null() || terminal_count < 2 {
+        return ptr::null_mut();
+    }
+    if obstacle_count > 0
+        && (obstacle_vertices.is_null() || vertex_counts.is_null() || weights.is_null())
+    {
+        return ptr::null_mut();
+    }
+    let terminals = std::slice::from_raw_parts(terminals, 2 * terminal_count)
+        .chunks_exact(2)
+        .map(|xy| (xy[0], xy[1]))
+        .collect();
+    let mut obstacles = Vec::new();
+    if obstacle_count > 0 {
+        let vertex_counts = std::slice::from_raw_parts(vertex_counts, obstacle_count);
+        if vertex_counts.iter().any(|&count| count < 3) {
+            return ptr::null_mut();
+        }
+        let weights = std::slice::from_raw_parts(weights, obstacle_count);
+        let total_vertices = vertex_counts.iter().sum::<usize>();
+        let mut vertices = std::slice::from_raw_parts(obstacle_vertices, 2 * total_vertices)
+            .chunks_exact(2)
+            .map(|xy| (xy[0], xy[1]));
+        for (&count, &weight) in vertex_counts.iter().zip(weights) {
+            // NaN and negative infinity are left for try_new to reject
+            let weight = if weight == f32::INFINITY { INF } else { weight };
+            let points = vertices.by_ref().take(count).collect();
+            obstacles.push(Obstacle::new(weight, points).compute_bounds());
+        }
+    }
+    let problem = match SteinerProblem::try_new(terminals, obstacles) {
+        Ok(problem) => problem,
+        Err(_) => return ptr::null_mut(),
+    };
+    catch_unwind(AssertUnwindSafe(|| {
+        let rng = rand_pcg::Pcg32::seed_from_u64(seed);
+        let stobga = StOBGA::new(rng, problem, POPULATION_SIZE, 1, 50, 50);
+        Box::into_raw(Box::new(StobgaHandle { stobga }))
+    }))
+    .unwrap_or(ptr::null_mut())
+}
+
+/// runs the algorithm for the given amount of generations, polishes the best
+/// individual and returns its total weight. Returns NaN for a null handle,
+/// or if the algorithm failed, after which the handle may only be freed.
+///
+/// # Safety
+/// `handle` must be null or a live handle obtained from [stobga_new].
+#[no_mangle]
+pub unsafe extern "C" fn stobga_run(handle: *mut StobgaHandle, generations: u32) -> f32 {
+    let handle = match handle.as_mut() {
+        Some(handle) => handle,
+        None => return f32::NAN,
+    };
+    catch_unwind(AssertUnwindSafe(|| {
+        for _ in 0..generations {
+            handle.stobga.step();
+        }
+        handle.stobga.finalize();
+        handle.stobga.population[0]
+            .minimum_spanning_tree
+            .as_ref()
+            .map_or(f32::NAN, |mst| mst.total_weight)
+    }))
+    .unwrap_or(f32::NAN)
+}
+
+/// copies the edges of the best tree into `buffer` as `x1, y1, x2, y2`
+/// quadruples, writing at most `capacity` edges (i.e. `4 * capacity`
+/// floats), and returns the number of edges in the tree. Passing a null
+/// buffer only queries that number. Returns 0 for a null handle.
+///
+/// # Safety
+/// `handle` must be null or a live handle obtained from [stobga_new], and a
+/// non-null `buffer` must have room for `4 * capacity` floats.
+#[no_mangle]
+pub unsafe extern "C" fn stobga_edges(
+    handle: *const StobgaHandle,
+    buffer: *mut f32,
+    capacity: usize,
+) -> usize {
+    let handle = match handle.as_ref() {
+        Some(handle) => handle,
+        None => return 0,
+    };
+    let graph = match handle.stobga.population[0].minimum_spanning_tree.as_ref() {
+        Some(mst) => &mst.graph,
+        None => return 0,
+    };
+    if !buffer.is_null() {
+        let buffer = std::slice::from_raw_parts_mut(buffer, 4 * capacity);
+        for (edge, out) in graph.edge_references().zip(buffer.chunks_exact_mut(4)) {
+            let from = graph[edge.source()];
+            let to = graph[edge.target()];
+            out.copy_from_slice(&[from.0, from.1, to.0, to.1]);
+        }
+    }
+    graph.edge_count()
+}
+
+/// releases a handle obtained from [stobga_new]. Null is ignored.
+///
+/// # Safety
+/// `handle` must be null or a live handle obtained from [stobga_new], which
+/// must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn stobga_free(handle: *mut StobgaHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
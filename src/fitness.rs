@@ -0,0 +1,43 @@
+//! objectives ranking the individuals. The one used by a run is chosen
+//! through [StobgaConfig::fitness](crate::StobgaConfig::fitness).
+
+use crate::{MinimumSpanningTree, SteinerProblem};
+
+pub trait Fitness {
+    /// the value of the tree, lower is better.
+    fn evaluate(&self, mst: &MinimumSpanningTree, problem: &SteinerProblem) -> f32;
+}
+
+/// the summed weight of all edges. This is the objective of the original
+/// paper.
+pub struct TotalWeight;
+
+/// the longest path between two terminals through the tree, e.g. the
+/// latency of a network. Weighted edges count with their cost.
+pub struct Bottleneck;
+
+impl Fitness for TotalWeight {
+    fn evaluate(&self, mst: &MinimumSpanningTree, _problem: &SteinerProblem) -> f32 {
+        mst.total_weight
+    }
+}
+
+impl Fitness for Bottleneck {
+    fn evaluate(&self, mst: &MinimumSpanningTree, problem: &SteinerProblem) -> f32 {
+        let graph = &mst.graph;
+        let terminals: Vec<_> = graph
+            .node_indices()
+            .filter(|&node| problem.terminals.contains(&graph[node]))
+            .collect();
+        let mut longest = 0.0f32;
+        for &terminal in &terminals {
+            let distances =
+                petgraph::algo::dijkstra(graph, terminal, None, |edge| *edge.weight());
+            for other in &terminals {
+                // terminals the tree does not reach are infinitely far away
+                longest = longest.max(distances.get(other).copied().unwrap_or(f32::INFINITY));
+            }
+        }
+        longest
+    }
+}
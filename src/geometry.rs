@@ -1,10 +1,10 @@
 pub const RADIANS_120_DEGREE: f32 = 2.0 * std::f32::consts::PI / 3.0;
 
-use std::{f32::INFINITY, vec};
+use std::f32::INFINITY;
 
 use itertools::Itertools;
 
-use crate::{Point, EPSILON};
+use crate::{Point, Pt, EPSILON};
 
 #[derive(Debug, Clone)]
 pub struct Bounds {
@@ -25,12 +25,179 @@ impl Default for Bounds {
     }
 }
 
+impl Bounds {
+    /// the smallest box containing all `points`, the (inverted) default
+    /// for none.
+    pub fn of_points<'a>(points: impl IntoIterator<Item = &'a Point>) -> Self {
+        let mut bounds = Bounds::default();
+        for &point in points {
+            let Pt { x, y } = point.into();
+            bounds.min_x = bounds.min_x.min(x);
+            bounds.min_y = bounds.min_y.min(y);
+            bounds.max_x = bounds.max_x.max(x);
+            bounds.max_y = bounds.max_y.max(y);
+        }
+        bounds
+    }
+
+    /// the area of the box, zero if it is empty or degenerate.
+    pub fn area(&self) -> f32 {
+        (self.max_x - self.min_x).max(0.0) * (self.max_y - self.min_y).max(0.0)
+    }
+}
+
 pub fn euclidean_distance(a: Point, b: Point) -> f32 {
     ((a.0 - b.0).powf(2.0) + (a.1 - b.1).powf(2.0)).sqrt()
 }
 
-pub fn overlap(x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32, x4: f32, y4: f32) -> bool {
-    !(x2 < x3 || x4 < x1 || y2 < y3 || y4 < y1)
+/// whether the two boxes share at least a point.
+pub fn overlap(a: &Bounds, b: &Bounds) -> bool {
+    !(a.max_x < b.min_x || b.max_x < a.min_x || a.max_y < b.min_y || b.max_y < a.min_y)
+}
+
+/// the side of the directed line a-b on which a point c lies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Collinear,
+}
+
+/// error bound of the f64 filter in [orientation], taken from Shewchuk's
+/// "Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric
+/// Predicates" (ccwerrboundA).
+const ORIENTATION_ERROR_BOUND: f64 = (3.0 + 16.0 * f64::EPSILON / 2.0) * f64::EPSILON / 2.0;
+
+/// exact sum and roundoff error of a + b.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+    (sum, (a - a_virtual) + (b - b_virtual))
+}
+
+/// the exact orientation of the triangle a, b, c.
+///
+/// The determinant is first evaluated in f64 and trusted whenever it is
+/// larger than its error bound. Otherwise it is expanded into six products,
+/// each of which is exact in f64 because the inputs are f32, and summed up
+/// without any rounding error as a floating point expansion. Hence the
+/// result never depends on rounding, and collinearity is detected exactly.
+pub fn orientation(a: Point, b: Point, c: Point) -> Orientation {
+    let (ax, ay) = (a.0 as f64, a.1 as f64);
+    let (bx, by) = (b.0 as f64, b.1 as f64);
+    let (cx, cy) = (c.0 as f64, c.1 as f64);
+    let det_left = (bx - ax) * (cy - ay);
+    let det_right = (by - ay) * (cx - ax);
+    let mut det = det_left - det_right;
+    if det.abs() <= ORIENTATION_ERROR_BOUND * (det_left.abs() + det_right.abs()) {
+        // grow a nonoverlapping expansion of increasing magnitude; its sign
+        // is the sign of its largest nonzero component.
+        let terms = [bx * cy, -bx * ay, -ax * cy, -by * cx, by * ax, ay * cx];
+        let mut expansion = [0.0; 6];
+        for (len, &term) in terms.iter().enumerate() {
+            let mut q = term;
+            for component in expansion.iter_mut().take(len) {
+                let (sum, error) = two_sum(q, *component);
+                *component = error;
+                q = sum;
+            }
+            expansion[len] = q;
+        }
+        det = expansion
+            .iter()
+            .rev()
+            .find(|&&component| component != 0.0)
+            .copied()
+            .unwrap_or(0.0);
+    }
+    if det > 0.0 {
+        Orientation::CounterClockwise
+    } else if det < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+/// the shortest distance between the point p and the segment a-b.
+pub fn point_segment_distance(p: Point, a: Point, b: Point) -> f32 {
+    let (dx, dy) = (b.0 as f64 - a.0 as f64, b.1 as f64 - a.1 as f64);
+    let (px, py) = (p.0 as f64 - a.0 as f64, p.1 as f64 - a.1 as f64);
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        ((px * dx + py * dy) / length_squared).clamp(0.0, 1.0)
+    };
+    ((px - t * dx).powi(2) + (py - t * dy).powi(2)).sqrt() as f32
+}
+
+/// the point of the segment a-b closest to p.
+pub fn closest_point_on_segment(p: Point, a: Point, b: Point) -> Point {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_squared = dx * dx + dy * dy;
+    if length_squared == 0.0 {
+        return a;
+    }
+    let t = (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / length_squared).clamp(0.0, 1.0);
+    (a.0 + t * dx, a.1 + t * dy)
+}
+
+/// `p` itself if it lies in the convex polygon `hull` (counterclockwise, as
+/// returned by [convex_hull]), otherwise the closest point of its boundary.
+pub fn clamp_to_convex_polygon(p: Point, hull: &[Point]) -> Point {
+    let inside = hull.len() > 2
+        && (0..hull.len()).all(|i| {
+            let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+            (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0) >= 0.0
+        });
+    if inside || hull.is_empty() {
+        return p;
+    }
+    (0..hull.len())
+        .map(|i| closest_point_on_segment(p, hull[i], hull[(i + 1) % hull.len()]))
+        .min_by(|a, b| euclidean_distance(*a, p).total_cmp(&euclidean_distance(*b, p)))
+        .unwrap()
+}
+
+/// the shortest distance between the segments a1-a2 and b1-b2, zero if
+/// they touch or cross.
+pub fn segment_segment_distance(a1: Point, a2: Point, b1: Point, b2: Point) -> f32 {
+    if segment_intersection(a1, a2, b1, b2).is_some() {
+        return 0.0;
+    }
+    point_segment_distance(a1, b1, b2)
+        .min(point_segment_distance(a2, b1, b2))
+        .min(point_segment_distance(b1, a1, a2))
+        .min(point_segment_distance(b2, a1, a2))
+}
+
+/// the shortest distance between the segment a-b and the boundary of
+/// `polygon`.
+pub fn segment_polygon_distance(a: Point, b: Point, polygon: &[Point]) -> f32 {
+    (0..polygon.len())
+        .map(|i| segment_segment_distance(a, b, polygon[i], polygon[(i + 1) % polygon.len()]))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// the angle (in radians) at `vertex` between the segments to `a` and `b`.
+/// The cosine is clamped to `[-1, 1]`, since rounding can push it slightly
+/// out of range for (nearly) collinear points, where `acos` yields `NaN`.
+pub fn angle(vertex: Point, a: Point, b: Point) -> f32 {
+    let v1 = (a.0 - vertex.0, a.1 - vertex.1);
+    let v2 = (b.0 - vertex.0, b.1 - vertex.1);
+    let dot = v1.0 * v2.0 + v1.1 * v2.1;
+    let den = (v1.0 * v1.0 + v1.1 * v1.1).sqrt() * (v2.0 * v2.0 + v2.1 * v2.1).sqrt();
+    (dot / den).clamp(-1.0, 1.0).acos()
+}
+
+/// the parameter along a-b at which the line a-b meets the line c-d.
+fn line_intersection_parameter(a: Point, b: Point, c: Point, d: Point) -> f64 {
+    let (x1, y1, x2, y2) = (a.0 as f64, a.1 as f64, b.0 as f64, b.1 as f64);
+    let (x3, y3, x4, y4) = (c.0 as f64, c.1 as f64, d.0 as f64, d.1 as f64);
+    let denom = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
+    ((x1 - x3) * (y3 - y4) - (y1 - y3) * (x3 - x4)) / denom
 }
 
 pub fn segment_segment_intersection(
@@ -44,31 +211,77 @@ pub fn segment_segment_intersection(
     y4: f32,
     point_overlap: bool,
 ) -> Option<Point> {
-    let denom = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
-    if denom == 0.0 {
+    let (a, b, c, d) = ((x1, y1), (x2, y2), (x3, y3), (x4, y4));
+    let o1 = orientation(a, b, c);
+    let o2 = orientation(a, b, d);
+    let o3 = orientation(c, d, a);
+    let o4 = orientation(c, d, b);
+    if o1 == o2 || o3 == o4 {
+        // parallel, collinear or both ends on the same side
         return None;
     }
-    let t = ((x1 - x3) * (y3 - y4) - (y1 - y3) * (x3 - x4)) / denom;
-    let u = ((x1 - x3) * (y1 - y2) - (y1 - y3) * (x1 - x2)) / denom;
-
-    let test = if point_overlap {
-        |t, u| 0.0 <= t && t <= 1.0 && 0.0 <= u && u <= 1.0
+    let touching = [o1, o2, o3, o4].contains(&Orientation::Collinear);
+    if touching && !point_overlap {
+        return None;
+    }
+    let t = line_intersection_parameter(a, b, c, d).clamp(0.0, 1.0) as f32;
+    let p = (x1 + t * (x2 - x1), y1 + t * (y2 - y1));
+    if point_overlap {
+        return Some(p);
     } else {
-        |t, u| 0.0 < t && t < 1.0 && 0.0 < u && u < 1.0
-    };
-    if test(t, u) {
-        let p = (x1 + t * (x2 - x1), y1 + t * (y2 - y1));
-        if point_overlap {
+        if p.0 != x3 && p.1 != y3 && p.0 != x4 && p.1 != y4 {
             return Some(p);
-        } else {
-            if p.0 != x3 && p.1 != y3 && p.0 != x4 && p.1 != y4 {
-                return Some(p);
-            }
         }
     }
     None
 }
 
+/// the point in which the segments a1-a2 and b1-b2 meet, endpoints included.
+/// Parallel segments yield `None`, unless they are collinear and merely touch
+/// in a single endpoint. Collinear segments sharing more than one point have
+/// no unique crossing and yield `None` as well.
+pub fn segment_intersection(a1: Point, a2: Point, b1: Point, b2: Point) -> Option<Point> {
+    let o1 = orientation(a1, a2, b1);
+    let o2 = orientation(a1, a2, b2);
+    if o1 == Orientation::Collinear && o2 == Orientation::Collinear {
+        let r = (a2.0 - a1.0, a2.1 - a1.1);
+        let rr = r.0 * r.0 + r.1 * r.1;
+        if rr == 0.0 {
+            return None;
+        }
+        // collinear: express b1 and b2 as parameters along a1-a2
+        let t0 = ((b1.0 - a1.0) * r.0 + (b1.1 - a1.1) * r.1) / rr;
+        let t1 = ((b2.0 - a1.0) * r.0 + (b2.1 - a1.1) * r.1) / rr;
+        let start = f32::max(0.0, t0.min(t1));
+        let end = f32::min(1.0, t0.max(t1));
+        if start == end {
+            return Some((a1.0 + start * r.0, a1.1 + start * r.1));
+        }
+        return None;
+    }
+    if o1 == o2 || orientation(b1, b2, a1) == orientation(b1, b2, a2) {
+        return None;
+    }
+    let t = line_intersection_parameter(a1, a2, b1, b2).clamp(0.0, 1.0) as f32;
+    Some((a1.0 + t * (a2.0 - a1.0), a1.1 + t * (a2.1 - a1.1)))
+}
+
+/// the edges of the polygon, starting with the one closing it from the last
+/// to the first point.
+pub fn polygon_edges(polygon: &[Point]) -> impl Iterator<Item = (Point, Point)> + Clone + '_ {
+    let n = polygon.len();
+    (0..n).map(move |i| (polygon[(i + n - 1) % n], polygon[i]))
+}
+
+/// the area enclosed by the polygon, whatever its orientation (shoelace
+/// formula).
+pub fn polygon_area(polygon: &[Point]) -> f32 {
+    let twice_area: f32 = polygon_edges(polygon)
+        .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+        .sum();
+    twice_area.abs() / 2.0
+}
+
 pub fn segment_polygon_intersection(
     x1: f32,
     y1: f32,
@@ -76,15 +289,22 @@ pub fn segment_polygon_intersection(
     y2: f32,
     polygon: &[Point],
     point_overlap: bool,
+) -> Vec<Point> {
+    segment_edges_intersection(x1, y1, x2, y2, polygon_edges(polygon), point_overlap)
+}
+
+/// like [segment_polygon_intersection], for a polygon given by its edges,
+/// e.g. as precomputed by [polygon_edges].
+pub fn segment_edges_intersection(
+    x1: f32,
+    y1: f32,
+    x2: f32,
+    y2: f32,
+    edges: impl IntoIterator<Item = (Point, Point)>,
+    point_overlap: bool,
 ) -> Vec<Point> {
     let mut result = Vec::new();
-    for i in -1..(polygon.len() - 1) as i32 {
-        let (x3, y3) = polygon[if i == -1 {
-            polygon.len() - 1
-        } else {
-            i as usize
-        }];
-        let (x4, y4) = polygon[(i + 1) as usize];
+    for ((x3, y3), (x4, y4)) in edges {
         let intersection =
             segment_segment_intersection(x1, y1, x2, y2, x3, y3, x4, y4, point_overlap);
         if intersection.is_some() {
@@ -176,36 +396,39 @@ pub fn middle(x1: f32, y1: f32, x2: f32, y2: f32) -> Point {
     (x1 + dx / 2.0, y1 + dy / 2.0)
 }
 
+/// whether (x1, y1) lies inside the polygon, decided by counting the polygon
+/// edges crossed by a ray towards positive x with the exact [orientation]
+/// predicate.
+///
+/// Points on the boundary are consistently classified as outside. This
+/// includes points closer to an edge than `EPSILON`, so that points computed
+/// from the polygon's own edges (e.g. their midpoints) are outside despite
+/// rounding errors.
 pub fn point_in_polygon(x1: f32, y1: f32, polygon: &[Point], _bounds: &Bounds) -> bool {
-    let mut mids = vec![];
-    {
-        let &(a,b) = polygon.last().unwrap();
-        let &(c,d) = polygon.first().unwrap();
-        mids.push(middle(a,b,c,d));
-    }
-    for j in 1..polygon.len() {
-        let i = j-1;
-        let (a,b) = polygon[i];
-        let (c,d) = polygon[j];
-        mids.push(middle(a, b, c, d));
-    }
-    let mut inside = 0;
-    let mut outside = 0;
-    for mid in mids.iter() {
-        let &(x2, y2) = mid;
-        let (dx, dy) = (x1-x2, y1-y2);
-        let length = euclidean_distance((0.0,0.0), (dx,dy));
-        let ray_length = 1000.0;
-        let factor = ray_length / length;
-        let (x2, y2) = (dx * factor, dy * factor);
-        let cuts = segment_polygon_intersection(x1, y1, x2, y2, polygon, true);
-        if cuts.len() % 2 == 1 {
-            inside+=1;
-        } else {
-            outside+=1;
+    point_inside_edges((x1, y1), polygon_edges(polygon))
+}
+
+/// like [point_in_polygon], for a polygon given by its edges.
+fn point_inside_edges(p: Point, edges: impl IntoIterator<Item = (Point, Point)>) -> bool {
+    let mut inside = false;
+    for (a, b) in edges {
+        if point_segment_distance(p, a, b) < EPSILON {
+            return false;
+        }
+        if (a.1 > p.1) != (b.1 > p.1) {
+            // the edge spans the ray's height; it is crossed if p lies on
+            // its left side when walking the edge upwards
+            let crossing_side = if a.1 < b.1 {
+                Orientation::CounterClockwise
+            } else {
+                Orientation::Clockwise
+            };
+            if orientation(a, b, p) == crossing_side {
+                inside = !inside;
+            }
         }
     }
-    return inside>outside
+    inside
 }
 
 fn _significantly_different(f1:f32, f2:f32) -> bool {
@@ -220,20 +443,54 @@ pub fn intersection_length(
     polygon: &[Point],
     bounds: &Bounds,
 ) -> f32 {
-    let mut cuts = segment_polygon_intersection(x1, y1, x2, y2, polygon, true);
+    intersection_length_and_entries(x1, y1, x2, y2, polygon, bounds).0
+}
+
+/// the length of the segment's part inside the polygon, together with the
+/// amount of separate pieces it consists of, i.e. how often the segment
+/// enters the polygon.
+pub fn intersection_length_and_entries(
+    x1: f32,
+    y1: f32,
+    x2: f32,
+    y2: f32,
+    polygon: &[Point],
+    _bounds: &Bounds,
+) -> (f32, usize) {
+    edges_intersection_length_and_entries(x1, y1, x2, y2, polygon_edges(polygon))
+}
+
+/// like [intersection_length_and_entries], for a polygon given by its
+/// edges, e.g. as precomputed by [polygon_edges].
+pub fn edges_intersection_length_and_entries(
+    x1: f32,
+    y1: f32,
+    x2: f32,
+    y2: f32,
+    edges: impl IntoIterator<Item = (Point, Point)> + Clone,
+) -> (f32, usize) {
+    let mut cuts = segment_edges_intersection(x1, y1, x2, y2, edges.clone(), true);
     cuts.push((x2, y2));
     cuts.insert(0, (x1, y1));
     let mut distance = 0.0;
+    let mut entries = 0;
+    let mut previous_inside = false;
     for i in 0..cuts.len() - 1 {
         let (x3, y3) = (cuts[i].0, cuts[i].1);
         let (x4, y4) = (cuts[i + 1].0, cuts[i + 1].1);
         let (mx, my) = middle(x3, y3, x4, y4);
-        if point_in_polygon(mx, my, polygon, bounds) {
+        let inside = point_inside_edges((mx, my), edges.clone());
+        if inside {
             distance += euclidean_distance((x3, y3), (x4, y4));
+            if !previous_inside {
+                entries += 1;
+            }
         }
+        previous_inside = inside;
     }
-    return distance;
+    (distance, entries)
 }
+
 pub fn fermat_point(a: Point, b: Point, c: Point, epsilon: f32) -> Point {
     use nalgebra::{Matrix2, Vector2};
 
@@ -307,4 +564,221 @@ pub fn fermat_point(a: Point, b: Point, c: Point, epsilon: f32) -> Point {
 
 pub fn centroid(a: Point, b: Point,c: Point) -> Point {
     ((a.0+b.0+c.0)/3.0,(a.1+b.1+c.1)/3.0)
-}
\ No newline at end of file
+}
+/// the convex hull of the points in counterclockwise order, without
+/// collinear points on its edges (Andrew's monotone chain).
+pub fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+    let mut hull: Vec<Point> = Vec::with_capacity(2 * sorted.len());
+    for pass in 0..2 {
+        let start = hull.len();
+        let chain: Box<dyn Iterator<Item = &Point>> = if pass == 0 {
+            Box::new(sorted.iter())
+        } else {
+            Box::new(sorted.iter().rev())
+        };
+        for &point in chain {
+            while hull.len() >= start + 2
+                && orientation(hull[hull.len() - 2], hull[hull.len() - 1], point)
+                    != Orientation::CounterClockwise
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // the last point is the first one of the other chain
+        hull.pop();
+    }
+    hull
+}
+
+/// the smallest circle containing all points, as center and radius
+/// (Welzl's algorithm in its iterative form).
+pub fn bounding_circle(points: &[Point]) -> (Point, f32) {
+    fn contains(circle: &(Point, f32), p: Point) -> bool {
+        euclidean_distance(circle.0, p) <= circle.1 + EPSILON * circle.1.max(1.0)
+    }
+    fn from_two(a: Point, b: Point) -> (Point, f32) {
+        let center = ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+        (center, euclidean_distance(a, b) / 2.0)
+    }
+    fn from_three(a: Point, b: Point, c: Point) -> (Point, f32) {
+        let (ax, ay) = (a.0 as f64, a.1 as f64);
+        let (bx, by) = (b.0 as f64 - ax, b.1 as f64 - ay);
+        let (cx, cy) = (c.0 as f64 - ax, c.1 as f64 - ay);
+        let d = 2.0 * (bx * cy - by * cx);
+        if orientation(a, b, c) == Orientation::Collinear || d == 0.0 {
+            // the circle over the two points farthest apart
+            return [from_two(a, b), from_two(a, c), from_two(b, c)]
+                .into_iter()
+                .max_by(|c1, c2| c1.1.total_cmp(&c2.1))
+                .unwrap();
+        }
+        let ux = (cy * (bx * bx + by * by) - by * (cx * cx + cy * cy)) / d;
+        let uy = (bx * (cx * cx + cy * cy) - cx * (bx * bx + by * by)) / d;
+        let center = ((ax + ux) as f32, (ay + uy) as f32);
+        (center, (ux * ux + uy * uy).sqrt() as f32)
+    }
+    let mut circle = match points.first() {
+        Some(&p) => (p, 0.0),
+        None => return ((0.0, 0.0), 0.0),
+    };
+    for i in 1..points.len() {
+        if contains(&circle, points[i]) {
+            continue;
+        }
+        circle = (points[i], 0.0);
+        for j in 0..i {
+            if contains(&circle, points[j]) {
+                continue;
+            }
+            circle = from_two(points[i], points[j]);
+            for k in 0..j {
+                if !contains(&circle, points[k]) {
+                    circle = from_three(points[i], points[j], points[k]);
+                }
+            }
+        }
+    }
+    circle
+}
+
+/// the rectangle of minimum area containing all points, as its corners in
+/// counterclockwise order. Such a rectangle has a side collinear with an
+/// edge of the convex hull, so every hull edge is tried as a side.
+pub fn min_area_rect(points: &[Point]) -> [Point; 4] {
+    let hull = convex_hull(points);
+    if hull.is_empty() {
+        return [(0.0, 0.0); 4];
+    }
+    let mut best: Option<(f32, [Point; 4])> = None;
+    for (i, &a) in hull.iter().enumerate() {
+        let b = hull[(i + 1) % hull.len()];
+        let length = euclidean_distance(a, b);
+        let u = if length > 0.0 {
+            ((b.0 - a.0) / length, (b.1 - a.1) / length)
+        } else {
+            (1.0, 0.0)
+        };
+        let v = (-u.1, u.0);
+        let (mut min_u, mut max_u) = (f32::INFINITY, f32::NEG_INFINITY);
+        let (mut min_v, mut max_v) = (f32::INFINITY, f32::NEG_INFINITY);
+        for p in &hull {
+            let (dx, dy) = (p.0 - a.0, p.1 - a.1);
+            let pu = dx * u.0 + dy * u.1;
+            let pv = dx * v.0 + dy * v.1;
+            min_u = min_u.min(pu);
+            max_u = max_u.max(pu);
+            min_v = min_v.min(pv);
+            max_v = max_v.max(pv);
+        }
+        let area = (max_u - min_u) * (max_v - min_v);
+        if best.as_ref().is_none_or(|(best_area, _)| area < *best_area) {
+            let corner = |s: f32, t: f32| (a.0 + u.0 * s + v.0 * t, a.1 + u.1 * s + v.1 * t);
+            best = Some((
+                area,
+                [
+                    corner(min_u, min_v),
+                    corner(max_u, min_v),
+                    corner(max_u, max_v),
+                    corner(min_u, max_v),
+                ],
+            ));
+        }
+    }
+    best.unwrap().1
+}
+
+/// a uniform scaling followed by a translation. Being uniform, it scales
+/// every length by `scale` and keeps angles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub scale: f32,
+    pub offset: (f32, f32),
+}
+
+impl Transform {
+    pub fn apply(&self, point: Point) -> Point {
+        (
+            point.0 * self.scale + self.offset.0,
+            point.1 * self.scale + self.offset.1,
+        )
+    }
+
+    /// the transform undoing this one.
+    pub fn inverse(&self) -> Transform {
+        Transform {
+            scale: 1.0 / self.scale,
+            offset: (-self.offset.0 / self.scale, -self.offset.1 / self.scale),
+        }
+    }
+}
+
+/// the centers of `k` clusters of the points, found by Lloyd's algorithm.
+/// The initial centers are picked deterministically, each one being the
+/// point farthest from the centers picked before, starting with the first
+/// point. Fewer than `k` centers are returned if there are fewer points.
+pub fn kmeans(points: &[Point], k: usize) -> Vec<Point> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut centers: Vec<Point> = Vec::new();
+    if let Some(&first) = points.first() {
+        centers.push(first);
+    }
+    while centers.len() < k.min(points.len()) {
+        let farthest = points
+            .iter()
+            .copied()
+            .max_by(|&a, &b| {
+                let distance = |p: Point| {
+                    centers
+                        .iter()
+                        .map(|&c| euclidean_distance(p, c))
+                        .fold(f32::INFINITY, f32::min)
+                };
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap();
+        centers.push(farthest);
+    }
+    let mut assignment = vec![usize::MAX; points.len()];
+    for _ in 0..100 {
+        let mut changed = false;
+        for (i, &p) in points.iter().enumerate() {
+            let nearest = (0..centers.len())
+                .min_by(|&a, &b| {
+                    euclidean_distance(p, centers[a]).total_cmp(&euclidean_distance(p, centers[b]))
+                })
+                .unwrap();
+            if assignment[i] != nearest {
+                assignment[i] = nearest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        for (c, center) in centers.iter_mut().enumerate() {
+            let members = points
+                .iter()
+                .zip(assignment.iter())
+                .filter(|(_, &a)| a == c)
+                .map(|(p, _)| *p)
+                .collect::<Vec<_>>();
+            if !members.is_empty() {
+                let n = members.len() as f32;
+                *center = (
+                    members.iter().map(|p| p.0).sum::<f32>() / n,
+                    members.iter().map(|p| p.1).sum::<f32>() / n,
+                );
+            }
+        }
+    }
+    centers
+}
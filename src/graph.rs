@@ -84,6 +84,11 @@ impl Graph {
     //     for self.edges_connected_to(node)
     // }
     pub fn minimum_spanning_tree(&self) -> Self {
+        self.mst_prim()
+    }
+    /// the minimum spanning tree grown from an arbitrary node by repeatedly
+    /// adding the cheapest edge leaving the tree (Prim).
+    pub fn mst_prim(&self) -> Self {
         fn add_edges(accumulator: &mut Vec<Edge>, other: &HashSet<Edge>, graph: &Graph) {
             for node in other {
                 accumulator.push(*node);
@@ -119,4 +124,215 @@ impl Graph {
         }
         Graph { nodes: visited, edges: accepted_edges }
     }
-}
\ No newline at end of file
+    /// the minimum spanning tree built from the edges in ascending order of
+    /// weight, skipping those that would close a cycle (Kruskal).
+    pub fn mst_kruskal(&self) -> Self {
+        let mut sorted_edges : Vec<(&Edge, &f32)> = self.edges.iter().collect();
+        sorted_edges.sort_by(|(_, w1), (_, w2)| w1.total_cmp(w2));
+        let mut parent : HashMap<OPoint, OPoint> = self.nodes.iter().map(|&node| (node, node)).collect();
+        let mut accepted_edges = HashMap::new();
+        for (edge, &weight) in sorted_edges {
+            let a = find_root(&mut parent, edge.start);
+            let b = find_root(&mut parent, edge.end);
+            if a != b {
+                parent.insert(a, b);
+                accepted_edges.insert(*edge, weight);
+            }
+        }
+        Graph { nodes: self.nodes.clone(), edges: accepted_edges }
+    }
+    /// one line per node, sorted by coordinates: `x,y: x1,y1=w1; x2,y2=w2`
+    /// followed by its neighbors and the weights of the connecting edges.
+    pub fn to_adjacency_string(&self) -> String {
+        let mut nodes : Vec<OPoint> = self.nodes.iter().copied().collect();
+        nodes.sort();
+        let mut lines = Vec::new();
+        for node in nodes {
+            let mut neighbors : Vec<(OPoint, f32)> = self.edges.iter()
+            .filter_map(|(edge, &weight)| if edge.start == node {
+                Some((edge.end, weight))
+            } else if edge.end == node {
+                Some((edge.start, weight))
+            } else {
+                None
+            })
+            .collect();
+            neighbors.sort_by_key(|neighbor| neighbor.0);
+            let neighbors = neighbors.iter()
+            .map(|(neighbor, weight)| format!("{},{}={}", neighbor.0, neighbor.1, weight))
+            .collect::<Vec<_>>()
+            .join("; ");
+            lines.push(format!("{},{}: {}", node.0, node.1, neighbors).trim_end().to_string());
+        }
+        lines.join("\n")
+    }
+    /// parses the format written by [Graph::to_adjacency_string]. `None` if
+    /// any line is malformed.
+    pub fn from_adjacency_string(input : &str) -> Option<Self> {
+        fn parse_point(text : &str) -> Option<Point> {
+            let (x, y) = text.trim().split_once(',')?;
+            Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+        }
+        let mut graph = Graph::new();
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let (node, neighbors) = line.split_once(':')?;
+            let node = parse_point(node)?;
+            graph.add_node(crate::util::to_graph(node));
+            for neighbor in neighbors.split(';').filter(|n| !n.trim().is_empty()) {
+                let (point, weight) = neighbor.split_once('=')?;
+                graph.add_edge_from_points(node, parse_point(point)?, weight.trim().parse().ok()?);
+            }
+        }
+        Some(graph)
+    }
+    /// the nodes of every connected component, found through union-find.
+    pub fn connected_components(&self) -> Vec<Vec<Point>> {
+        let mut parent : HashMap<OPoint, OPoint> = self.nodes.iter().map(|&node| (node, node)).collect();
+        for edge in self.edges.keys() {
+            let a = find_root(&mut parent, edge.start);
+            let b = find_root(&mut parent, edge.end);
+            if a != b {
+                parent.insert(a, b);
+            }
+        }
+        let mut components : HashMap<OPoint, Vec<Point>> = HashMap::new();
+        for &node in self.nodes.iter() {
+            let root = find_root(&mut parent, node);
+            components.entry(root).or_default().push(crate::util::to_point(node));
+        }
+        components.into_values().collect()
+    }
+}
+
+/// the representative of the union-find set containing `node`
+fn find_root(parent : &mut HashMap<OPoint, OPoint>, node : OPoint) -> OPoint {
+    let mut root = node;
+    while parent[&root] != root {
+        root = parent[&root];
+    }
+    // path compression
+    let mut node = node;
+    while node != root {
+        let next = parent[&node];
+        parent.insert(node, root);
+        node = next;
+    }
+    root
+}
+
+/// a minimum spanning arborescence (Chu-Liu/Edmonds) over `node_count`
+/// nodes, directed away from `root`. `edges` holds `(from, to, weight)`
+/// triples; every node has to be reachable from the root. The result holds
+/// the chosen triples.
+pub fn minimum_arborescence(
+    node_count: usize,
+    root: usize,
+    edges: &[(usize, usize, f32)],
+) -> Vec<(usize, usize, f32)> {
+    let indexed: Vec<_> = edges
+        .iter()
+        .enumerate()
+        .map(|(i, &(from, to, weight))| (from, to, weight, i))
+        .collect();
+    arborescence_edges(node_count, root, &indexed)
+        .into_iter()
+        .map(|i| edges[i])
+        .collect()
+}
+
+/// the recursive contraction step, working on `(from, to, weight, id)`
+/// quadruples and returning the ids of the chosen edges.
+fn arborescence_edges(
+    node_count: usize,
+    root: usize,
+    edges: &[(usize, usize, f32, usize)],
+) -> Vec<usize> {
+    // the cheapest incoming edge of every node
+    let mut incoming: Vec<Option<usize>> = vec![None; node_count];
+    for (position, &(from, to, weight, _)) in edges.iter().enumerate() {
+        if from == to || to == root {
+            continue;
+        }
+        match incoming[to] {
+            Some(best) if edges[best].2 <= weight => {}
+            _ => incoming[to] = Some(position),
+        }
+    }
+    let parent = |node: usize| edges[incoming[node].expect("node unreachable from root")].0;
+
+    // find the cycles formed by the cheapest incoming edges
+    let mut component = vec![usize::MAX; node_count];
+    let mut walk_mark = vec![usize::MAX; node_count];
+    let mut component_count = 0;
+    let mut cycles = Vec::new();
+    for start in 0..node_count {
+        let mut node = start;
+        while node != root && component[node] == usize::MAX && walk_mark[node] != start {
+            walk_mark[node] = start;
+            node = parent(node);
+        }
+        if node != root && component[node] == usize::MAX {
+            // closed a cycle during this walk
+            let mut cycle = vec![node];
+            component[node] = component_count;
+            let mut member = parent(node);
+            while member != node {
+                component[member] = component_count;
+                cycle.push(member);
+                member = parent(member);
+            }
+            cycles.push(cycle);
+            component_count += 1;
+        }
+    }
+    if cycles.is_empty() {
+        return (0..node_count)
+            .filter(|&node| node != root)
+            .map(|node| edges[incoming[node].unwrap()].3)
+            .collect();
+    }
+    for entry in component.iter_mut() {
+        if *entry == usize::MAX {
+            *entry = component_count;
+            component_count += 1;
+        }
+    }
+
+    // contract every cycle into a single node and solve recursively
+    let in_cycle = |node: usize| cycles.iter().any(|cycle| cycle.contains(&node));
+    let mut contracted = Vec::new();
+    let mut original = Vec::new();
+    for (position, &(from, to, weight, _)) in edges.iter().enumerate() {
+        if component[from] == component[to] {
+            continue;
+        }
+        let weight = if in_cycle(to) {
+            weight - edges[incoming[to].unwrap()].2
+        } else {
+            weight
+        };
+        contracted.push((component[from], component[to], weight, contracted.len()));
+        original.push(position);
+    }
+    let chosen: Vec<usize> = arborescence_edges(component_count, component[root], &contracted)
+        .into_iter()
+        .map(|i| original[i])
+        .collect();
+
+    // expand the cycles again, dropping the cycle edge into the node where
+    // the tree enters the cycle
+    let mut result: Vec<usize> = chosen.iter().map(|&position| edges[position].3).collect();
+    for cycle in &cycles {
+        let entered = chosen
+            .iter()
+            .map(|&position| edges[position].1)
+            .find(|to| cycle.contains(to))
+            .unwrap();
+        for &member in cycle {
+            if member != entered {
+                result.push(edges[incoming[member].unwrap()].3);
+            }
+        }
+    }
+    result
+}
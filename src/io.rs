@@ -0,0 +1,101 @@
+//! reading instances from their text representation.
+use std::io::BufRead;
+
+use crate::{Obstacle, Point, SolverError, SteinerProblem, INF};
+
+/// parses a terminal file: a header line followed by one `x,y` line per
+/// terminal. A third field names the terminal.
+pub fn parse_terminals(input: &str) -> (Vec<Point>, Vec<Option<String>>) {
+    read_terminals(input.as_bytes())
+}
+
+/// like [parse_terminals], but reading line by line from `reader`.
+pub fn read_terminals(reader: impl BufRead) -> (Vec<Point>, Vec<Option<String>>) {
+    let mut terminals = Vec::new();
+    let mut labels = Vec::new();
+    let lines = reader
+        .lines()
+        .map(|line| line.expect("could not read terminals"));
+    for line in lines.skip(1).filter(|line| !line.trim().is_empty()) {
+        let fields = line.split(',').collect::<Vec<_>>();
+        let coordinate = |i: usize| -> f32 {
+            fields[i]
+                .trim()
+                .parse()
+                .expect("could not parse terminal coordinate")
+        };
+        terminals.push((coordinate(0), coordinate(1)));
+        labels.push(
+            fields
+                .get(2)
+                .map(|label| label.trim().to_string())
+                .filter(|label| !label.is_empty()),
+        );
+    }
+    (terminals, labels)
+}
+
+/// parses an obstacle file. Obstacles are separated by empty lines (or a
+/// lone `,`) and consist of a weight line (`max` for solid obstacles), an
+/// optional `name:<name>` line, an optional `entry:<cost>` line, an
+/// optional `slope:<x>,<y>` line for directional obstacles and one `x,y`
+/// line per corner.
+pub fn parse_obstacles(input: &str) -> Vec<Obstacle> {
+    read_obstacles(input.as_bytes())
+}
+
+/// like [parse_obstacles], but reading line by line from `reader`.
+pub fn read_obstacles(reader: impl BufRead) -> Vec<Obstacle> {
+    let mut obstacles = Vec::new();
+    let mut current_obstacle = Obstacle::new(0.0, vec![]);
+    for line in reader.lines() {
+        let line = line.expect("could not read obstacles");
+        let line = line.as_str();
+        if line.is_empty() || line == "," {
+            obstacles.push(current_obstacle.compute_bounds());
+            current_obstacle = Obstacle::new(0.0, vec![]);
+        } else if line.to_lowercase().starts_with("max") {
+            current_obstacle.weight = INF
+        } else if let Some(name) = line.strip_prefix("name:") {
+            current_obstacle.name = Some(name.trim().to_string());
+        } else if let Some(cost) = line.strip_prefix("entry:") {
+            current_obstacle.entry_cost = cost.trim().parse().expect("could not parse entry cost");
+        } else if let Some(slope) = line.strip_prefix("slope:") {
+            let (x, y) = slope.split_once(',').expect("could not parse slope");
+            current_obstacle.directional = Some((
+                x.trim().parse().expect("could not parse slope"),
+                y.trim().parse().expect("could not parse slope"),
+            ));
+        } else {
+            let fields = line.split(',').collect::<Vec<_>>();
+            if fields.get(1) == Some(&"") || fields.len() < 2 {
+                current_obstacle.weight = fields[0].parse().expect("could not parse weight");
+            } else {
+                current_obstacle.points.push((
+                    fields[0]
+                        .parse()
+                        .expect("could not parse obstacle coordinate"),
+                    fields[1]
+                        .parse()
+                        .expect("could not parse obstacle coordinate"),
+                ));
+            }
+        }
+    }
+    obstacles.push(current_obstacle.compute_bounds());
+    obstacles
+}
+
+impl SteinerProblem {
+    /// reads a terminal and an obstacle file, e.g. from stdin, keeping the
+    /// terminal labels. Fails on non-finite coordinates such as `inf` and on
+    /// obstacle weights that are not positive.
+    pub fn from_readers(
+        terminals: impl BufRead,
+        obstacles: impl BufRead,
+    ) -> Result<SteinerProblem, SolverError> {
+        let (terminals, labels) = read_terminals(terminals);
+        Ok(SteinerProblem::try_new(terminals, read_obstacles(obstacles))?
+            .with_terminal_labels(labels))
+    }
+}
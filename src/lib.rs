@@ -0,0 +1,5682 @@
+pub mod batch;
+mod config;
+pub mod corners;
+pub mod crossover;
+mod error;
+mod export;
+pub mod ffi;
+pub mod fitness;
+pub mod geometry;
+pub mod graph;
+pub mod io;
+pub mod pareto;
+mod quadtree;
+mod report;
+mod solution;
+pub mod space3d;
+pub mod termination;
+pub mod util;
+pub mod wasm;
+mod wkt;
+
+pub use config::{InitStrategy, ReplacementStrategy, StobgaConfig};
+pub use error::SolverError;
+pub use report::{
+    history_to_csv, GenerationReport, Generations, InstanceStats, PhaseTimings, Operator, OperatorCount, OperatorStats,
+    Summary,
+};
+use corners::Corners;
+use geometry::euclidean_distance;
+use geometry::fermat_point;
+use geometry::overlap;
+use geometry::Bounds;
+use indexmap::IndexSet;
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+use petgraph::data::FromElements;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+pub use solution::{Solution, SolutionCheck, SolutionDiff};
+
+use rand::seq::SliceRandom;
+use rand::{distributions::Uniform, prelude::Distribution, Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use util::{in_region, SteinerRegion};
+use util::to_graph;
+use util::to_point;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::SystemTime;
+
+use pareto::ParetoArchive;
+use quadtree::LazyQuadTree;
+use termination::{Composite, MaxGenerations, Stagnation, Termination};
+
+/// a location in 2D
+pub type Point = (f32, f32);
+
+/// a location in 2D with named coordinates, converting to and from
+/// [Point] and [OPoint]. New code should prefer it over indexing tuples.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pt {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Pt {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Point> for Pt {
+    fn from((x, y): Point) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Pt> for Point {
+    fn from(p: Pt) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl From<OPoint> for Pt {
+    fn from((x, y): OPoint) -> Self {
+        Self { x: *x, y: *y }
+    }
+}
+
+impl From<Pt> for OPoint {
+    fn from(p: Pt) -> Self {
+        (OrderedFloat(p.x), OrderedFloat(p.y))
+    }
+}
+
+pub const POPULATION_SIZE: usize = 500;
+/// the minimum multiplier to the average terminal distance by which a Steiner
+/// point will be moved. In the original paper this value is always used after
+/// 1000 generations have passed.
+const M_RANGE_MIN: f32 = 0.01;
+/// the smallest probability by which a flip_move_mutation is going to occur.
+const P_FLIP_MOVE_MIN: f32 = 0.6;
+const P_FLIP_MOVE_MAX: f32 = 0.99;
+/// how often a Steiner point inside a solid obstacle is tried to be moved
+/// to a free spot before it gets dropped.
+const REPAIR_ATTEMPTS: usize = 20;
+/// how often a flip-move of a Steiner point onto a terminal is resampled
+/// before the point is left where it is.
+const MOVE_ATTEMPTS: usize = 20;
+/// how often a random Steiner point is drawn before adding one is given up,
+/// e.g. because solid obstacles cover (nearly) everything.
+const SAMPLE_ATTEMPTS: usize = 100;
+/// the most passes of moving Steiner points to Fermat points in
+/// [StOBGA::finalize].
+const FINALIZE_ITERATIONS: usize = 100;
+/// the most passes of [optimize_fixed_topology].
+const RELAXATION_ITERATIONS: usize = 10000;
+/// the share by which adaptive mutation probabilities move towards the
+/// improvement rates of the last generation.
+const ADAPTATION_RATE: f32 = 0.5;
+/// the mutation operators, in the order of [StOBGA]'s mutation
+/// probabilities.
+const MUTATIONS: [Operator; 3] = [Operator::FlipMove, Operator::AddSteiner, Operator::RemoveSteiner];
+/// represents an infinitely large value without getting dangerously close to
+/// the limits of this datatype.
+pub const INF: f32 = 1e10;
+/// a small value, usually utilized to make up for floating point imprecisions.
+pub const EPSILON: f32 = 1e-6;
+/// amount of generations the algorithm continues whilst not finding
+/// a better individual before ending
+pub const RECESSION_DURATION: usize = 500;
+/// the relative amount by which the best weight has to drop to count as an
+/// improvement of the population.
+pub const IMPROVEMENT_FACTOR: f32 = 0.01 / 100.0;
+
+enum BufferSelector {
+    ChildBuffer,
+    Population
+}
+
+/// represents a Steiner Problem instance, consisting of terminals, obstacles
+/// and their corners, the centroids obtained through Delaunay triangulation,
+/// bounds and the average distance between terminals
+#[derive(Clone)]
+pub struct SteinerProblem {
+    /// a list of all the terminals to be connected
+    terminals: Vec<Point>,
+    /// a list of all the obstacles present on the plane
+    pub(crate) obstacles: Vec<Obstacle>,
+    /// a list of all the obstacles' corners
+    obstacle_corners: Vec<Point>,
+    /// a list to store the centroids of the triangles, obtained through
+    /// Delaunay triangulation
+    centroids: Vec<Point>,
+    /// the left, topmost and right, bottommost coordinates framing all
+    /// terminals and obstacles in a square
+    bounds: Bounds,
+    /// where random Steiner points are drawn from, if not within `bounds`
+    sampling_bounds: Option<Bounds>,
+    /// the mean distance between terminals
+    average_terminal_distance: f32,
+    /// optional names of the terminals. They are only carried along for the
+    /// output and do not influence the algorithm.
+    terminal_labels: Vec<Option<String>>,
+    /// the indices (in the input) of the terminals merged into an earlier
+    /// one within `EPSILON`
+    duplicate_terminals: Vec<usize>,
+    /// the weight of the minimum spanning tree of the terminals alone, see
+    /// [StOBGA::terminal_mst_weight]. Computed on first request.
+    terminal_mst_weight: OnceLock<f32>,
+}
+
+impl SteinerProblem {
+    /// constructor taking a vector of terminals (Points) and a list of
+    /// Obstacles as its arguments. Panics on non-finite coordinates, see
+    /// [SteinerProblem::try_new].
+    pub fn new(terminals: Vec<Point>, obstacles: Vec<Obstacle>) -> Self {
+        Self::try_new(terminals, obstacles).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// like [SteinerProblem::new], but failing with
+    /// [SolverError::NonFiniteCoordinate] if a terminal or obstacle corner
+    /// has a `NaN` or infinite coordinate, and with
+    /// [SolverError::NonPositiveWeight] or [SolverError::NegativeEntryCost]
+    /// for obstacles that would make edges cost nothing or less.
+    pub fn try_new(terminals: Vec<Point>, obstacles: Vec<Obstacle>) -> Result<Self, SolverError> {
+        for (index, obstacle) in obstacles.iter().enumerate() {
+            if obstacle.weight.is_nan() || obstacle.weight <= 0.0 {
+                return Err(SolverError::NonPositiveWeight {
+                    obstacle: index,
+                    weight: obstacle.weight,
+                });
+            }
+            if obstacle.entry_cost < 0.0 {
+                return Err(SolverError::NegativeEntryCost {
+                    obstacle: index,
+                    entry_cost: obstacle.entry_cost,
+                });
+            }
+        }
+        let all_points = terminals
+            .iter()
+            .chain(obstacles.iter().flat_map(|obstacle| obstacle.points.iter()));
+        for &(x, y) in all_points {
+            if !x.is_finite() || !y.is_finite() {
+                return Err(SolverError::NonFiniteCoordinate { x, y });
+            }
+        }
+        // coincident terminals would only add zero-length edges
+        let mut unique_terminals: Vec<Point> = Vec::with_capacity(terminals.len());
+        let mut duplicate_terminals = Vec::new();
+        for (index, terminal) in terminals.into_iter().enumerate() {
+            if unique_terminals
+                .iter()
+                .any(|&other| euclidean_distance(other, terminal) < EPSILON)
+            {
+                log::warn!("merging duplicate terminal {:?}", terminal);
+                duplicate_terminals.push(index);
+            } else {
+                unique_terminals.push(terminal);
+            }
+        }
+        let terminals = unique_terminals;
+        let mut obstacle_corners = Vec::new();
+        for obstacle in &obstacles {
+            for point in &obstacle.points {
+                obstacle_corners.push(*point);
+            }
+        }
+        let mut centroids = Vec::new();
+        let vertices = terminals
+            .iter()
+            .chain(obstacle_corners.iter())
+            .map(|(x, y)| delaunator::Point {
+                x: *x as f64,
+                y: *y as f64,
+            })
+            .collect::<Vec<_>>();
+        // the triangles come as consecutive triples of vertex indices. For
+        // fewer than three or only collinear vertices there are none, and
+        // there are no centroids either.
+        let mut triangles = Vec::new();
+        for triple in delaunator::triangulate(&vertices)
+            .triangles
+            .as_slice()
+            .chunks_exact(3)
+        {
+            triangles.push([
+                (vertices[triple[0]].x as f32, vertices[triple[0]].y as f32),
+                (vertices[triple[1]].x as f32, vertices[triple[1]].y as f32),
+                (vertices[triple[2]].x as f32, vertices[triple[2]].y as f32),
+            ]);
+        }
+        for [a, b, c] in triangles {
+            centroids.push(geometry::centroid(a, b, c));
+        }
+
+        let mut bounds = Bounds::of_points(terminals.iter().chain(obstacle_corners.iter()));
+        // collinear input spans no area, but random Steiner points are drawn
+        // from the bounds, so a flat side is widened to the other side's
+        // extent (or 1 for a single point)
+        let width = bounds.max_x - bounds.min_x;
+        let height = bounds.max_y - bounds.min_y;
+        let padding = width.max(height).max(1.0) / 2.0;
+        if (0.0..EPSILON).contains(&width) {
+            bounds.min_x -= padding;
+            bounds.max_x += padding;
+        }
+        if (0.0..EPSILON).contains(&height) {
+            bounds.min_y -= padding;
+            bounds.max_y += padding;
+        }
+        let mut average_terminal_distance = 0.0;
+        {
+            let n = terminals.len();
+            for i in 0..n {
+                for j in 0..n {
+                    average_terminal_distance += euclidean_distance(terminals[i], terminals[j]);
+                }
+            }
+            if n > 1 {
+                average_terminal_distance /= (n*(n-1)) as f32;
+            }
+        }
+
+        Ok(SteinerProblem {
+            terminal_labels: vec![None; terminals.len()],
+            terminals,
+            obstacles,
+            obstacle_corners,
+            centroids,
+            bounds,
+            sampling_bounds: None,
+            average_terminal_distance,
+            duplicate_terminals,
+            terminal_mst_weight: OnceLock::new(),
+        })
+    }
+
+    /// names the terminals, in the same order as they were passed to
+    /// [SteinerProblem::new]. Labels of merged duplicate terminals are
+    /// dropped.
+    pub fn with_terminal_labels(mut self, labels: Vec<Option<String>>) -> Self {
+        assert_eq!(
+            labels.len(),
+            self.terminals.len() + self.duplicate_terminals.len(),
+            "every terminal needs a (possibly empty) label"
+        );
+        self.terminal_labels = labels
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !self.duplicate_terminals.contains(index))
+            .map(|(_, label)| label)
+            .collect();
+        self
+    }
+
+    /// lets random Steiner points be drawn from `bounds` instead of the
+    /// bounds of the terminals and obstacles, e.g. to allow them in a
+    /// larger region. The derived bounds still frame the svg output.
+    pub fn with_sampling_bounds(mut self, bounds: Bounds) -> Self {
+        self.sampling_bounds = Some(bounds);
+        self
+    }
+
+    /// the bounds of all terminals and obstacle corners, widened where
+    /// they are flat.
+    pub fn bounds(&self) -> Bounds {
+        self.bounds.clone()
+    }
+
+    /// the region random Steiner points are drawn from, see
+    /// [SteinerProblem::with_sampling_bounds].
+    pub fn sampling_bounds(&self) -> &Bounds {
+        self.sampling_bounds.as_ref().unwrap_or(&self.bounds)
+    }
+
+    /// the centroids of the Delaunay triangles over the terminals and
+    /// obstacle corners, which seed the t1 individuals.
+    pub fn centroids(&self) -> &[Point] {
+        &self.centroids
+    }
+
+    /// the same problem scaled into the unit square, so that absolute
+    /// constants like `EPSILON` fit its scale, together with the transform
+    /// that was applied. Entry costs are scaled along with the lengths, so
+    /// weights of the normalized problem are the original weights times the
+    /// transform's scale.
+    pub fn normalized(self) -> (SteinerProblem, geometry::Transform) {
+        let width = self.bounds.max_x - self.bounds.min_x;
+        let height = self.bounds.max_y - self.bounds.min_y;
+        let extent = width.max(height);
+        let scale = if extent > 0.0 { 1.0 / extent } else { 1.0 };
+        let transform = geometry::Transform {
+            scale,
+            offset: (-self.bounds.min_x * scale, -self.bounds.min_y * scale),
+        };
+        let terminals = self.terminals.iter().map(|&p| transform.apply(p)).collect();
+        let obstacles = self
+            .obstacles
+            .into_iter()
+            .map(|obstacle| {
+                Obstacle {
+                    points: obstacle.points.iter().map(|&p| transform.apply(p)).collect(),
+                    entry_cost: obstacle.entry_cost * scale,
+                    ..obstacle
+                }
+                .compute_bounds()
+            })
+            .collect();
+        let mut problem =
+            SteinerProblem::new(terminals, obstacles).with_terminal_labels(self.terminal_labels);
+        if let Some(bounds) = self.sampling_bounds {
+            let (min_x, min_y) = transform.apply((bounds.min_x, bounds.min_y));
+            let (max_x, max_y) = transform.apply((bounds.max_x, bounds.max_y));
+            problem = problem.with_sampling_bounds(Bounds {
+                min_x,
+                max_x,
+                min_y,
+                max_y,
+            });
+        }
+        (problem, transform)
+    }
+
+    /// the centers of an `nx` by `ny` grid of cells over the sampling bounds, leaving
+    /// out those inside solid obstacles.
+    fn grid_points(&self, nx: usize, ny: usize) -> Vec<Point> {
+        let bounds = self.sampling_bounds();
+        let width = (bounds.max_x - bounds.min_x) / nx as f32;
+        let height = (bounds.max_y - bounds.min_y) / ny as f32;
+        (0..ny)
+            .flat_map(|j| (0..nx).map(move |i| (i, j)))
+            .map(|(i, j)| {
+                (
+                    bounds.min_x + (i as f32 + 0.5) * width,
+                    bounds.min_y + (j as f32 + 0.5) * height,
+                )
+            })
+            .filter(|&p| !self.coordinates_in_solid_obstacle(p))
+            .collect()
+    }
+
+    /// whether the segment from a to b runs through a solid obstacle.
+    pub(crate) fn segment_crosses_solid_obstacle(&self, a: Point, b: Point) -> bool {
+        self.obstacles
+            .iter()
+            .any(|obstacle| obstacle.weight == INF && obstacle.intersects_segment(a, b))
+    }
+
+    /// for each of the first `k` obstacle corners the probability to include
+    /// it under [InitStrategy::CornersNearTerminals].
+    fn corner_probabilities(&self, k: usize) -> Vec<f64> {
+        let scale = self.average_terminal_distance.max(EPSILON) as f64;
+        self.obstacle_corners[..k]
+            .iter()
+            .map(|&corner| {
+                let distance = self
+                    .terminals
+                    .iter()
+                    .map(|&terminal| euclidean_distance(corner, terminal))
+                    .fold(f32::INFINITY, f32::min);
+                (-(distance as f64) / scale).exp()
+            })
+            .collect()
+    }
+
+    /// whether the segment from a to b runs through an obstacle that is not
+    /// solid, i.e. pays a penalty there.
+    pub(crate) fn segment_crosses_weighted_obstacle(&self, a: Point, b: Point) -> bool {
+        self.obstacles
+            .iter()
+            .any(|obstacle| obstacle.weight < INF && obstacle.intersects_segment(a, b))
+    }
+
+    /// a function to check whether a given point is located inside a
+    /// solid obstacle
+    pub(crate) fn coordinates_in_solid_obstacle(&self, coordinates: Point) -> bool {
+        self.obstacles
+            .iter()
+            .any(|obstacle| obstacle.weight == INF && obstacle.contains(coordinates))
+    }
+
+    /// whether `coordinates` lie within `EPSILON` of a terminal, where a
+    /// Steiner point would only add a zero-length edge.
+    fn coordinates_on_terminal(&self, coordinates: Point) -> bool {
+        self.terminals
+            .iter()
+            .any(|&terminal| euclidean_distance(terminal, coordinates) < EPSILON)
+    }
+
+    /// whether every edge costs the same in both directions, i.e. no
+    /// obstacle is directional
+    fn is_symmetric(&self) -> bool {
+        self.obstacles.iter().all(|obstacle| obstacle.directional.is_none())
+    }
+
+    /// the cost of the straight edge from `from` to `to`: its length, with
+    /// the parts inside obstacles weighted by them. Crossing a solid
+    /// obstacle costs `INF`, or `config.solid_penalty` per length if given.
+    /// Passing within `config.clearance` of an obstacle adds the depth of
+    /// the intrusion into that margin.
+    ///
+    /// Edges longer than `config.max_edge_length` cost `INF`, or pay
+    /// `config.max_edge_length_penalty` per excess length.
+    ///
+    /// Where obstacles overlap, the edge pays the highest weight among them
+    /// rather than their sum, so a region that is solid in any of them is
+    /// solid. Entry costs are paid for every weighted obstacle entered.
+    pub(crate) fn edge_cost(&self, from: OPoint, to: OPoint, config: &StobgaConfig) -> f32 {
+        let p1 = to_point(from);
+        let p2 = to_point(to);
+        let mut length = geometry::euclidean_distance(p1, p2);
+        let mut excess_length_cost = 0.0;
+        if let Some(limit) = config.max_edge_length.filter(|&limit| length > limit) {
+            match config.max_edge_length_penalty {
+                Some(penalty) => excess_length_cost = (length - limit) * penalty,
+                None => return INF,
+            }
+        }
+        let direction = if length > 0.0 {
+            ((p2.0 - p1.0) / length, (p2.1 - p1.1) / length)
+        } else {
+            (0.0, 0.0)
+        };
+        let margin = config.clearance;
+        // widened by the clearance, and by EPSILON so that rounding does
+        // not skip edges grazing an obstacle
+        let widening = margin + EPSILON;
+        let line_bounds = Bounds {
+            min_x: p1.0.min(p2.0) - widening,
+            min_y: p1.1.min(p2.1) - widening,
+            max_x: p1.0.max(p2.0) + widening,
+            max_y: p1.1.max(p2.1) + widening,
+        };
+        let mut crossed = Vec::new();
+        for obstacle in &self.obstacles {
+            if overlap(&line_bounds, &obstacle.bounds) {
+                let (intersection_len, entries) = geometry::edges_intersection_length_and_entries(
+                    *from.0,
+                    *from.1,
+                    *to.0,
+                    *to.1,
+                    obstacle.edges.iter().copied(),
+                );
+                if intersection_len > 0.0 {
+                    crossed.push((obstacle, intersection_len));
+                    if obstacle.weight != INF {
+                        length += entries as f32 * obstacle.entry_cost;
+                    }
+                }
+                if margin > 0.0 {
+                    let distance = geometry::segment_polygon_distance(p1, p2, &obstacle.points);
+                    if distance < margin {
+                        length += margin - distance;
+                    }
+                }
+            }
+        }
+        // the pieces of the edge inside obstacles with the weight paid on
+        // them, `INF` for solid ones
+        let pieces: Vec<(f32, f32)> = match crossed[..] {
+            [] => vec![],
+            [(obstacle, intersection_len)] => vec![(
+                intersection_len,
+                if obstacle.weight == INF {
+                    INF
+                } else {
+                    obstacle.weight_along(direction)
+                },
+            )],
+            _ => {
+                // cut the edge at every boundary crossing of any of the
+                // obstacles, and weigh each piece by the heaviest obstacle
+                // containing its middle
+                let mut cuts = vec![p1, p2];
+                for (obstacle, _) in &crossed {
+                    cuts.extend(geometry::segment_edges_intersection(
+                        p1.0,
+                        p1.1,
+                        p2.0,
+                        p2.1,
+                        obstacle.edges.iter().copied(),
+                        true,
+                    ));
+                }
+                cuts.sort_by(|&a, &b| {
+                    euclidean_distance(p1, a).total_cmp(&euclidean_distance(p1, b))
+                });
+                cuts.windows(2)
+                    .filter_map(|cut| {
+                        let (mx, my) = geometry::middle(cut[0].0, cut[0].1, cut[1].0, cut[1].1);
+                        crossed
+                            .iter()
+                            .filter(|(obstacle, _)| {
+                                geometry::point_in_polygon(mx, my, &obstacle.points, &obstacle.bounds)
+                            })
+                            .map(|(obstacle, _)| {
+                                if obstacle.weight == INF {
+                                    INF
+                                } else {
+                                    obstacle.weight_along(direction)
+                                }
+                            })
+                            .max_by(|a, b| a.total_cmp(b))
+                            .map(|weight| (euclidean_distance(cut[0], cut[1]), weight))
+                    })
+                    .collect()
+            }
+        };
+        for (piece_len, weight) in pieces {
+            length -= piece_len;
+            if weight == INF {
+                match config.solid_penalty {
+                    Some(penalty) => length += piece_len * penalty,
+                    None => return INF,
+                }
+            } else {
+                length += piece_len * weight;
+            }
+        }
+        length + excess_length_cost
+    }
+
+    /// the cost of the edge from `from` to `to`, looked up in `cache`
+    /// first. The reverse edge is only reused while all costs are
+    /// symmetric.
+    fn cached_edge_cost(
+        &self,
+        cache: &mut HashMap<(OPoint, OPoint), f32>,
+        from: OPoint,
+        to: OPoint,
+        config: &StobgaConfig,
+    ) -> f32 {
+        if let Some(&x) = cache.get(&(from, to)) {
+            return x;
+        }
+        if self.is_symmetric() {
+            if let Some(&x) = cache.get(&(to, from)) {
+                return x;
+            }
+        }
+        let d = self.edge_cost(from, to, config);
+        cache.insert((from, to), d);
+        d
+    }
+
+    /// the costs of all edges between two terminals under `config`, row by
+    /// row: the edge from terminal `i` to terminal `j` is at `i * n + j`.
+    pub(crate) fn terminal_distances(&self, config: &StobgaConfig) -> Vec<f32> {
+        let mut distances = Vec::with_capacity(self.terminals.len().pow(2));
+        for &from in &self.terminals {
+            for &to in &self.terminals {
+                distances.push(self.edge_cost(to_graph(from), to_graph(to), config));
+            }
+        }
+        distances
+    }
+
+    /// the minimum spanning tree over the terminals and the Steiner points
+    /// and included corners of `chromosome`, with edge costs taken from and
+    /// added to `cache`. Edges between two terminals are looked up in
+    /// `terminal_distances` instead, if given (see
+    /// [SteinerProblem::terminal_distances]). With directional obstacles it
+    /// is a minimum arborescence instead.
+    pub(crate) fn spanning_tree(
+        &self,
+        chromosome: &Chromosome,
+        cache: &mut HashMap<(OPoint, OPoint), f32>,
+        config: &StobgaConfig,
+        terminal_distances: Option<&[f32]>,
+    ) -> MinimumSpanningTree {
+        let mut graph = petgraph::graph::UnGraph::new_undirected();
+        // the Steiner points and corners are sorted so that chromosomes
+        // holding the same ones build the same graph, whatever order they
+        // were inserted in
+        let mut steiner_points: Vec<OPoint> = chromosome.steiner_points.iter().copied().collect();
+        steiner_points.sort_unstable();
+        let mut corners: Vec<usize> = chromosome.included_corners.iter().collect();
+        corners.sort_unstable();
+        let source_vertices = steiner_points
+            .into_iter()
+            .chain(
+                corners
+                    .into_iter()
+                    .map(|c| util::to_graph(self.obstacle_corners[c])),
+            )
+            .chain(self.terminals.iter().map(|p| to_graph(*p)));
+        // coincident vertices would be connected through a zero-length edge,
+        // adding a node to the tree without adding any cost.
+        let mut unique_vertices = IndexSet::new();
+        for vertex in source_vertices {
+            if !unique_vertices.insert(vertex) && cfg!(debug_assertions) {
+                log::warn!(
+                    "skipping zero-length edge at coincident vertex {:?}",
+                    to_point(vertex)
+                );
+            }
+        }
+        for vertex in unique_vertices.iter() {
+            graph.add_node(to_point(*vertex));
+        }
+        let mut terminal_of = vec![None; unique_vertices.len()];
+        if terminal_distances.is_some() {
+            for (j, terminal) in self.terminals.iter().enumerate() {
+                if let Some(i) = unique_vertices.get_index_of(&to_graph(*terminal)) {
+                    terminal_of[i] = Some(j);
+                }
+            }
+        }
+        let n = self.terminals.len();
+        let mut cost = |i1: usize, t1: OPoint, i2: usize, t2: OPoint| {
+            match (terminal_of[i1], terminal_of[i2], terminal_distances) {
+                (Some(j1), Some(j2), Some(distances)) => distances[j1 * n + j2],
+                _ => self.cached_edge_cost(cache, t1, t2, config),
+            }
+        };
+        let mst = if self.is_symmetric() {
+            for pair in unique_vertices.into_iter().enumerate().combinations(2) {
+                let (i1, t1) = pair[0];
+                let (i2, t2) = pair[1];
+                let length = cost(i1, t1, i2, t2);
+                graph.add_edge(
+                    petgraph::graph::NodeIndex::new(i1),
+                    petgraph::graph::NodeIndex::new(i2),
+                    length,
+                );
+            }
+            petgraph::graph::UnGraph::<_, _>::from_elements(
+                petgraph::algo::min_spanning_tree(&graph),
+            )
+        } else {
+            // with directional obstacles the tree is a minimum arborescence
+            // directed away from the first terminal.
+            let root = unique_vertices
+                .get_index_of(&to_graph(self.terminals[0]))
+                .unwrap();
+            let mut candidates = Vec::new();
+            for (i1, &t1) in unique_vertices.iter().enumerate() {
+                for (i2, &t2) in unique_vertices.iter().enumerate() {
+                    if i1 != i2 {
+                        let length = cost(i1, t1, i2, t2);
+                        candidates.push((i1, i2, length));
+                    }
+                }
+            }
+            for (from, to, length) in
+                crate::graph::minimum_arborescence(unique_vertices.len(), root, &candidates)
+            {
+                graph.add_edge(
+                    petgraph::graph::NodeIndex::new(from),
+                    petgraph::graph::NodeIndex::new(to),
+                    length,
+                );
+            }
+            graph
+        };
+        // a tree needing an impassable edge (e.g. to a walled off terminal)
+        // is infeasible however many of them it needs, summing them up would
+        // only rank the infeasible trees by a meaningless large number
+        let infeasible = mst.edge_weights().any(|&weight| weight >= INF);
+        let total_distance = if infeasible {
+            INF
+        } else {
+            mst.edge_weights().sum::<f32>()
+        };
+        let mut mst = MinimumSpanningTree {
+            total_weight: total_distance,
+            fitness: total_distance,
+            graph: mst,
+        };
+        mst.fitness = if infeasible {
+            INF
+        } else {
+            config.fitness.evaluate(&mst, self)
+        };
+        mst
+    }
+
+    /// the weight of the tree [StOBGA] builds for `chromosome` (with solid
+    /// obstacles impassable), computed without any solver state. Handy for
+    /// benchmarking the cost function.
+    pub fn evaluate(&self, chromosome: &Chromosome) -> f32 {
+        self.evaluate_with_cache(chromosome, &mut HashMap::new())
+    }
+
+    /// like [SteinerProblem::evaluate], reusing the edge costs in `cache`
+    /// across calls.
+    pub fn evaluate_with_cache(
+        &self,
+        chromosome: &Chromosome,
+        cache: &mut HashMap<(OPoint, OPoint), f32>,
+    ) -> f32 {
+        self.spanning_tree(chromosome, cache, &StobgaConfig::default(), None)
+            .total_weight
+    }
+}
+
+/// an extension to the usual Point data structure. This one can be hashed and
+/// therefore be stored in a HashSet, IndexSet or IndexMap.
+pub type OPoint = (OrderedFloat<f32>, OrderedFloat<f32>);
+
+/// Chromosomes are one of the two building blocks of Individuals.
+/// Being the genotype, they hold the crucial information to build the
+/// genotype and evaluate its objective function.
+///
+/// Genotypes contain all Steiner Points an Individual might have.
+/// Steiner Points can be stored as Points with 2D coordinates,
+/// or through an index for the list of obstacle corners.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Chromosome {
+    steiner_points: IndexSet<OPoint>,
+    included_corners: Corners,
+    /// the Steiner points for nearest neighbor queries, cleared by
+    /// [Chromosome::steiner_points_mut]
+    #[serde(skip)]
+    steiner_point_tree: LazyQuadTree,
+}
+
+impl Chromosome {
+    pub fn new(
+        steiner_points: impl IntoIterator<Item = Point>,
+        included_corners: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        Self {
+            steiner_points: steiner_points.into_iter().map(to_graph).collect(),
+            included_corners: included_corners.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn steiner_points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.steiner_points.iter().map(|p| to_point(*p))
+    }
+
+    /// the Steiner points for changing them, which drops the quadtree over
+    /// them.
+    fn steiner_points_mut(&mut self) -> &mut IndexSet<OPoint> {
+        self.steiner_point_tree.clear();
+        &mut self.steiner_points
+    }
+
+    /// the Steiner point closest to `p` and its distance, from a quadtree
+    /// that is rebuilt on the first query after the Steiner points changed.
+    fn nearest_steiner_point(&self, p: Point) -> Option<(Point, f32)> {
+        self.steiner_point_tree.nearest(|| self.steiner_points(), p)
+    }
+
+    /// the indices of the included obstacle corners
+    pub fn included_corners(&self) -> impl Iterator<Item = usize> + '_ {
+        self.included_corners.iter()
+    }
+
+    /// moves the Steiner points outside of the convex polygon `hull` onto
+    /// its boundary.
+    fn clamp_to_hull(&mut self, hull: &[Point]) {
+        if self
+            .steiner_points
+            .iter()
+            .all(|&p| geometry::clamp_to_convex_polygon(to_point(p), hull) == to_point(p))
+        {
+            return;
+        }
+        let clamped = self
+            .steiner_points
+            .iter()
+            .map(|&p| to_graph(geometry::clamp_to_convex_polygon(to_point(p), hull)))
+            .collect();
+        *self.steiner_points_mut() = clamped;
+    }
+}
+
+impl std::fmt::Debug for Chromosome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = format!("{:?}", self.included_corners);
+        let len = string.len();
+        f.write_str(
+            format!(
+                "Chromosome(steinerPoints={:?}, includedObstacleCornersIndices=set([{}]))",
+                self.steiner_points
+                    .iter()
+                    .map(|p| to_point(*p))
+                    .collect::<Vec<Point>>(),
+                string.chars().skip(1).take(len - 2).collect::<String>()
+            )
+            .as_str(),
+        )
+    }
+}
+
+/// Small wrapper around a [
+/// petgraph::UnGraph](../petgraph/graph/type.UnGraph.html)
+/// data structure to cache its summed edge weights.
+#[derive(Clone)]
+pub struct MinimumSpanningTree {
+    pub total_weight: f32,
+    /// the value of the tree under [StobgaConfig::fitness], the total weight
+    /// by default
+    pub fitness: f32,
+    pub graph: petgraph::graph::UnGraph<Point, f32, u32>,
+}
+
+/// what a node of a tree stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    Terminal,
+    /// a corner of an obstacle
+    Corner,
+    /// a free Steiner point
+    Steiner,
+}
+
+/// an edge of a [MinimumSpanningTree] together with the kinds of its
+/// endpoints, see [MinimumSpanningTree::edges_classified].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassifiedEdge {
+    pub from: Point,
+    pub to: Point,
+    pub from_kind: NodeKind,
+    pub to_kind: NodeKind,
+    pub weight: f32,
+}
+
+impl ClassifiedEdge {
+    /// whether both endpoints are terminals.
+    pub fn connects_terminals(&self) -> bool {
+        self.from_kind == NodeKind::Terminal && self.to_kind == NodeKind::Terminal
+    }
+}
+
+impl MinimumSpanningTree {
+    /// the edges of the tree, each endpoint classified by matching it
+    /// against the terminals and obstacle corners of `problem`. Points that
+    /// are neither are free Steiner points.
+    pub fn edges_classified(&self, problem: &SteinerProblem) -> Vec<ClassifiedEdge> {
+        let kind = |p: Point| {
+            if problem.terminals.contains(&p) {
+                NodeKind::Terminal
+            } else if problem.obstacle_corners.contains(&p) {
+                NodeKind::Corner
+            } else {
+                NodeKind::Steiner
+            }
+        };
+        self.graph
+            .edge_references()
+            .map(|edge| {
+                let (from, to) = (self.graph[edge.source()], self.graph[edge.target()]);
+                ClassifiedEdge {
+                    from,
+                    to,
+                    from_kind: kind(from),
+                    to_kind: kind(to),
+                    weight: *edge.weight(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Together a [Chromosome] and a [SteinerProblem] for an Individual.
+/// An Individual represents a potential solution that can be evaluated.
+/// Individuals are part of [StOBGA]'s population.
+/// Individuals can be mutated and crossed over to create new Individuals
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Individual {
+    pub chromosome: Chromosome,
+    /// not serialized, it is rebuilt from the chromosome
+    #[serde(skip)]
+    pub minimum_spanning_tree: Option<MinimumSpanningTree>,
+}
+
+/// the weight (if the minimum spanning tree is built) and the amount of
+/// Steiner points and obstacle corners. Without the problem at hand, a
+/// weight below `INF` is taken as feasible.
+impl std::fmt::Display for Individual {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.minimum_spanning_tree {
+            Some(mst) => write!(f, "weight {}, ", mst.total_weight)?,
+            None => write!(f, "not evaluated, ")?,
+        }
+        write!(
+            f,
+            "{} Steiner points, {} obstacle corners",
+            self.chromosome.steiner_points.len(),
+            self.chromosome.included_corners.iter().count()
+        )?;
+        match &self.minimum_spanning_tree {
+            Some(mst) if mst.total_weight < INF => write!(f, ", feasible"),
+            Some(_) => write!(f, ", infeasible"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// solves the instance of `terminals` and `obstacles` in one go: sets up
+/// the problem and a [StOBGA] seeded with 0, [runs](StOBGA::run) it with
+/// `config` and returns the best solution found. Panics on non-finite
+/// coordinates, see [SteinerProblem::try_new].
+pub fn solve(terminals: Vec<Point>, obstacles: Vec<Obstacle>, config: StobgaConfig) -> Solution {
+    solve_with_summary(terminals, obstacles, config).0
+}
+
+/// like [solve], also returning the [Summary] of the run with the time and
+/// the function evaluations it took.
+pub fn solve_with_summary(
+    terminals: Vec<Point>,
+    obstacles: Vec<Obstacle>,
+    config: StobgaConfig,
+) -> (Solution, Summary) {
+    let problem = SteinerProblem::new(terminals, obstacles);
+    let mut stobga = StOBGA::<rand_pcg::Pcg32>::from_seed(0, problem, config);
+    stobga.run();
+    (stobga.best_solution(), stobga.summary())
+}
+
+/// the current instant, `None` on wasm where there is no clock.
+fn now() -> Option<Instant> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return Some(Instant::now());
+    #[cfg(target_arch = "wasm32")]
+    None
+}
+
+/// a quick answer without any randomness, e.g. as a baseline for the
+/// genetic algorithm. Starting from the minimum spanning tree of the
+/// terminals, the Fermat point of the sharpest angle below 120° in the
+/// tree is added as long as that makes the tree lighter; if it does not,
+/// the next sharpest angle is tried.
+pub fn greedy_steiner(problem: &SteinerProblem) -> Solution {
+    let config = StobgaConfig::default();
+    let mut cache = HashMap::new();
+    let chromosome = Chromosome::new(vec![], vec![]);
+    let mut individual = Individual {
+        minimum_spanning_tree: Some(problem.spanning_tree(&chromosome, &mut cache, &config, None)),
+        chromosome,
+    };
+    loop {
+        let graph = &individual.minimum_spanning_tree.as_ref().unwrap().graph;
+        let mut candidates: Vec<(f32, Point)> = individual
+            .add_steiner_candidates(geometry::RADIANS_120_DEGREE)
+            .into_iter()
+            .map(|(i1, i2, i3)| {
+                let (p1, p2, p3) = (graph[i1], graph[i2], graph[i3]);
+                (geometry::angle(p1, p2, p3), fermat_point(p1, p2, p3, EPSILON))
+            })
+            .filter(|&(_, p)| {
+                !problem.coordinates_in_solid_obstacle(p)
+                    && !problem.coordinates_on_terminal(p)
+                    && individual
+                        .chromosome
+                        .nearest_steiner_point(p)
+                        .is_none_or(|(_, distance)| distance > 1e-2)
+            })
+            .collect();
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let weight = individual.weight();
+        let improvement = candidates.into_iter().find_map(|(_, p)| {
+            let mut chromosome = individual.chromosome.clone();
+            chromosome.steiner_points_mut().insert(to_graph(p));
+            let mst = problem.spanning_tree(&chromosome, &mut cache, &config, None);
+            (mst.total_weight < weight - EPSILON).then_some((chromosome, mst))
+        });
+        match improvement {
+            Some((chromosome, mst)) => {
+                individual.chromosome = chromosome;
+                individual.minimum_spanning_tree = Some(mst);
+            }
+            None => break,
+        }
+    }
+    Solution::from_individual(&individual, problem)
+}
+
+/// the Steiner points of `topology` moved to where the tree over that very
+/// topology is shortest, and the cost of the tree. Nodes of `topology`
+/// that are not terminals of `problem` are the Steiner points, their
+/// coordinates are the starting positions. Pass after pass each Steiner
+/// point moves to the Fermat point of its neighbors if it has three, or
+/// towards their geometric median otherwise (Weiszfeld), until no point
+/// moves farther than `EPSILON`. Obstacles only count for the cost. The
+/// points are returned in the order of their starting positions.
+pub fn optimize_fixed_topology(topology: &graph::Graph, problem: &SteinerProblem) -> (Vec<Point>, f32) {
+    let mut steiner_points: Vec<OPoint> = topology
+        .nodes
+        .iter()
+        .copied()
+        .filter(|&node| !problem.terminals.contains(&to_point(node)))
+        .collect();
+    steiner_points.sort_unstable();
+    let index: HashMap<OPoint, usize> = steiner_points
+        .iter()
+        .enumerate()
+        .map(|(i, &node)| (node, i))
+        .collect();
+    // a neighbor is either another Steiner point or a fixed terminal
+    let mut neighbors: Vec<Vec<Result<usize, Point>>> = vec![Vec::new(); steiner_points.len()];
+    for edge in topology.edges.keys() {
+        for (from, to) in [(edge.start, edge.end), (edge.end, edge.start)] {
+            if let Some(&i) = index.get(&from) {
+                neighbors[i].push(index.get(&to).copied().ok_or(to_point(to)));
+            }
+        }
+    }
+    let mut positions: Vec<Point> = steiner_points.iter().map(|&p| to_point(p)).collect();
+    for _ in 0..RELAXATION_ITERATIONS {
+        let mut largest_move = 0.0f32;
+        for i in 0..positions.len() {
+            let around: Vec<Point> = neighbors[i]
+                .iter()
+                .map(|neighbor| neighbor.map_or_else(|p| p, |j| positions[j]))
+                .collect();
+            let moved = match around[..] {
+                [] => positions[i],
+                [a, b, c] => fermat_point(a, b, c, EPSILON),
+                _ => {
+                    let (mut sum, mut weights) = ((0.0, 0.0), 0.0);
+                    for &p in &around {
+                        let w = 1.0 / euclidean_distance(p, positions[i]).max(EPSILON);
+                        sum = (sum.0 + p.0 * w, sum.1 + p.1 * w);
+                        weights += w;
+                    }
+                    (sum.0 / weights, sum.1 / weights)
+                }
+            };
+            largest_move = largest_move.max(euclidean_distance(moved, positions[i]));
+            positions[i] = moved;
+        }
+        if largest_move < EPSILON {
+            break;
+        }
+    }
+    let position = |node: OPoint| index.get(&node).map_or(to_point(node), |&i| positions[i]);
+    let config = StobgaConfig::default();
+    let cost = topology
+        .edges
+        .keys()
+        .map(|edge| {
+            problem.edge_cost(to_graph(position(edge.start)), to_graph(position(edge.end)), &config)
+        })
+        .sum();
+    (positions, cost)
+}
+
+impl<R: Rng + SeedableRng> StOBGA<R> {
+    /// like [StOBGA::with_config], with the generator of type `R` seeded
+    /// from `seed`. The same seed and generator type yield the same run.
+    pub fn from_seed(seed: u64, problem: SteinerProblem, config: StobgaConfig) -> Self {
+        Self::with_config(R::seed_from_u64(seed), problem, config)
+    }
+}
+
+pub struct StOBGA<R: Rng> {
+    problem: SteinerProblem,
+    config: StobgaConfig,
+    pub population: Vec<Individual>,
+    random_generator: R,
+    pub current_generation: usize,
+    child_buffer: Vec<Individual>,
+    /// the children in the child buffer stemming from each crossover, and
+    /// the weight of their lighter parent, for the crossover statistics
+    crossovers: Vec<(Range<usize>, f32)>,
+    pub function_evaluations: u64,
+    /// the amount of edge costs actually computed, i.e. not found in the
+    /// cache. Unlike `function_evaluations` it grows with the size of the
+    /// trees.
+    pub edge_evaluations: u64,
+    phase_timings: PhaseTimings,
+    edge_db: HashMap<(OPoint, OPoint), f32>,
+    /// see [SteinerProblem::terminal_distances]
+    terminal_distances: Vec<f32>,
+    /// the convex hull of the terminals, if Steiner points are kept in it
+    terminal_hull: Option<Vec<Point>>,
+    operator_stats: OperatorStats,
+    /// the operator statistics at the start of the generation
+    previous_operator_stats: OperatorStats,
+    /// the chances of the [MUTATIONS] with adaptive mutation
+    mutation_probabilities: [f32; 3],
+    /// the trade-offs between weight and Steiner points seen so far
+    pareto: ParetoArchive,
+    /// the weight of the best individual when it last improved by
+    /// [IMPROVEMENT_FACTOR]
+    best_weight: f32,
+    /// the generation after which `best_weight` was reached
+    best_found_generation: usize,
+    #[cfg(not(target_arch = "wasm32"))]
+    start_time: SystemTime,
+}
+
+impl<R: Rng> StOBGA<R> {
+    fn crossover(&mut self, parent_1_index: usize, parent_2_index: usize) {
+        let (child_1, child_2) = self.config.crossover.recombine(
+            &self.population[parent_1_index].chromosome,
+            &self.population[parent_2_index].chromosome,
+            &self.problem,
+            &mut self.random_generator,
+        );
+        let parent_weight = f32::min(
+            self.population[parent_1_index].weight(),
+            self.population[parent_2_index].weight(),
+        );
+        // forget crossovers whose children left the buffer
+        let len = self.child_buffer.len();
+        self.crossovers.retain(|(children, _)| children.end <= len);
+        for mut chromosome in [child_1, child_2] {
+            if self.config.repair_children {
+                self.repair(&mut chromosome);
+            }
+            self.child_buffer.push(Individual {
+                chromosome,
+                minimum_spanning_tree: None,
+            });
+            if self.config.single_child {
+                self.build_mst(self.child_buffer.len() - 1, BufferSelector::ChildBuffer);
+            }
+        }
+        self.crossovers.push((len..len + self.children_per_crossover(), parent_weight));
+        if self.config.single_child {
+            let second = self.child_buffer.pop().unwrap();
+            let first = self.child_buffer.last_mut().unwrap();
+            if second.weight() < first.weight() {
+                *first = second;
+            }
+        }
+    }
+
+    /// the number of new individuals to create every generation, a third of
+    /// the population. In the original StOBGA this value is fixed at 166.
+    fn number_offspring(&self) -> usize {
+        self.config.population_size / 3
+    }
+
+    /// the amount of children a crossover adds to the child buffer.
+    fn children_per_crossover(&self) -> usize {
+        if self.config.single_child {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// relocates every Steiner point of `chromosome` lying in a solid
+    /// obstacle or outside the Steiner region to a random valid spot,
+    /// dropping it after `REPAIR_ATTEMPTS` failed tries.
+    fn repair(&mut self, chromosome: &mut Chromosome) {
+        let region = self.config.steiner_region();
+        let bounds = self.problem.sampling_bounds();
+        let x_dist = Uniform::new(bounds.min_x, bounds.max_x);
+        let y_dist = Uniform::new(bounds.min_y, bounds.max_y);
+        let invalid: Vec<OPoint> = chromosome
+            .steiner_points
+            .iter()
+            .copied()
+            .filter(|p| {
+                self.problem.coordinates_in_solid_obstacle(to_point(*p))
+                    || !in_region(to_point(*p), region)
+            })
+            .collect();
+        for point in invalid {
+            chromosome.steiner_points_mut().shift_remove(&point);
+            for _ in 0..REPAIR_ATTEMPTS {
+                let candidate = (
+                    self.random_generator.sample(x_dist),
+                    self.random_generator.sample(y_dist),
+                );
+                if !self.problem.coordinates_in_solid_obstacle(candidate)
+                    && in_region(candidate, region)
+                {
+                    chromosome.steiner_points_mut().insert(to_graph(candidate));
+                    break;
+                }
+            }
+        }
+    }
+
+    fn mutate_flip_move(&mut self, index: usize) {
+        self.child_buffer[index].mutation_flip_move(
+            &self.problem,
+            &mut self.random_generator,
+            self.current_generation,
+            self.config.corner_count(&self.problem),
+            self.config.steiner_region(),
+        );
+        if self.child_buffer[index].minimum_spanning_tree.is_none() {
+            self.build_mst(index, BufferSelector::ChildBuffer);
+        }
+    }
+
+    fn mutate_add_steiner(&mut self, index: usize) {
+        if self.child_buffer[index].minimum_spanning_tree.is_none() {
+            self.build_mst(index, BufferSelector::ChildBuffer);
+        }
+        self.child_buffer[index].mutation_add_steiner(
+            &self.problem,
+            &mut self.random_generator,
+            self.config.add_steiner_angle_threshold,
+            self.config.steiner_region(),
+        );
+        if self.child_buffer[index].minimum_spanning_tree.is_none() {
+            self.build_mst(index, BufferSelector::ChildBuffer);
+        }
+    }
+
+    /// removes a Steiner point of degree two or less, unless that makes a
+    /// feasible tree cross a solid obstacle, in which case the removal is
+    /// reverted.
+    fn mutate_remove_steiner(&mut self, index: usize) {
+        if self.child_buffer[index].minimum_spanning_tree.is_none() {
+            self.build_mst(index, BufferSelector::ChildBuffer);
+        }
+        let original = self.child_buffer[index].clone();
+        self.child_buffer[index].mutation_remove_steiner(&self.problem, &mut self.random_generator);
+        if self.child_buffer[index].minimum_spanning_tree.is_none() {
+            self.build_mst(index, BufferSelector::ChildBuffer);
+        }
+        let feasible = |individual: &Individual| {
+            individual.minimum_spanning_tree.as_ref().unwrap().total_weight < INF
+        };
+        if feasible(&original) && !feasible(&self.child_buffer[index]) {
+            self.child_buffer[index] = original;
+        }
+    }
+
+    fn mutate(&mut self, index: usize) {
+        let operator = if self.config.adaptive_mutation.is_some() {
+            let mut r = self.random_generator.gen::<f32>();
+            let mut chosen = MUTATIONS[MUTATIONS.len() - 1];
+            for (operator, probability) in MUTATIONS.iter().zip(self.mutation_probabilities) {
+                if r < probability {
+                    chosen = *operator;
+                    break;
+                }
+                r -= probability;
+            }
+            chosen
+        } else {
+            self.scheduled_mutation()
+        };
+        // only adding and removing Steiner points need the tree before the
+        // mutation. Flip-move children without one are compared to their
+        // parents instead of building a tree just for the statistics.
+        if operator != Operator::FlipMove
+            && self.child_buffer[index].minimum_spanning_tree.is_none()
+        {
+            self.build_mst(index, BufferSelector::ChildBuffer);
+        }
+        let weight_before = match &self.child_buffer[index].minimum_spanning_tree {
+            Some(mst) => mst.total_weight,
+            None => self
+                .crossovers
+                .iter()
+                .find(|(children, _)| children.contains(&index))
+                .map_or(f32::NAN, |&(_, parent_weight)| parent_weight),
+        };
+        match operator {
+            Operator::FlipMove => self.mutate_flip_move(index),
+            Operator::AddSteiner => self.mutate_add_steiner(index),
+            _ => self.mutate_remove_steiner(index),
+        }
+        let improved = self.child_buffer[index].weight() < weight_before;
+        self.operator_stats.record(operator, improved);
+    }
+
+    /// flip-move with a probability dropping over the first 1000
+    /// generations, otherwise adding or removing a Steiner point.
+    fn scheduled_mutation(&mut self) -> Operator {
+        let p_flip_move = f32::max(
+            P_FLIP_MOVE_MAX * (1.0 - (self.current_generation as f32) / 1000.0),
+            P_FLIP_MOVE_MIN,
+        );
+        if self.random_generator.gen_bool(p_flip_move as f64) {
+            Operator::FlipMove
+        } else if self.random_generator.gen_bool(0.5) {
+            Operator::AddSteiner
+        } else {
+            Operator::RemoveSteiner
+        }
+    }
+
+    /// records every crossover whose children are in the child buffer as
+    /// improving if one of them, by now mutated, beats the lighter parent.
+    fn record_crossovers(&mut self) {
+        for (children, parent_weight) in std::mem::take(&mut self.crossovers) {
+            // children may have been dropped to fill the population exactly
+            let end = children.end.min(self.child_buffer.len());
+            let improved = self
+                .child_buffer
+                .get(children.start..end)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|child| child.minimum_spanning_tree.as_ref())
+                .any(|mst| mst.total_weight < parent_weight);
+            self.operator_stats.record(Operator::Crossover, improved);
+        }
+    }
+
+    /// moves the mutation probabilities towards the improvement rates the
+    /// operators had since the last call, keeping each above `floor`.
+    fn adapt_mutation_probabilities(&mut self, floor: f32) {
+        let floor = floor.clamp(0.0, 1.0 / MUTATIONS.len() as f32);
+        let rates = MUTATIONS.map(|operator| {
+            let now = self.operator_stats.get(operator);
+            let before = self.previous_operator_stats.get(operator);
+            let applications = now.applications - before.applications;
+            if applications == 0 {
+                0.0
+            } else {
+                (now.improvements - before.improvements) as f32 / applications as f32
+            }
+        });
+        self.previous_operator_stats = self.operator_stats.clone();
+        let total: f32 = rates.iter().sum();
+        if total == 0.0 {
+            return;
+        }
+        let share = 1.0 - floor * MUTATIONS.len() as f32;
+        for (probability, rate) in self.mutation_probabilities.iter_mut().zip(rates) {
+            let target = floor + share * rate / total;
+            *probability += ADAPTATION_RATE * (target - *probability);
+        }
+    }
+
+    /// removes every Steiner point and obstacle corner of degree two from
+    /// the tree of the individual at `index` whose removal does not make the
+    /// tree more expensive. Its neighbors get connected directly, or through
+    /// whatever the rebuilt minimum spanning tree prefers.
+    fn remove_degree_two_steiner_points(&mut self, index: usize) {
+        'search: loop {
+            let original = self.population[index].clone();
+            let mst = original.minimum_spanning_tree.as_ref().unwrap();
+            let candidates: Vec<Point> = mst
+                .graph
+                .node_indices()
+                .filter(|&node| mst.graph.edges(node).count() == 2)
+                .map(|node| mst.graph[node])
+                .collect();
+            for candidate in candidates {
+                let mut chromosome = original.chromosome.clone();
+                let removed_point = chromosome.steiner_points_mut().remove(&to_graph(candidate));
+                let corner = (0..self.problem.obstacle_corners.len()).find(|&corner| {
+                    self.problem.obstacle_corners[corner] == candidate
+                        && chromosome.included_corners.contains(&corner)
+                });
+                if let Some(corner) = corner {
+                    chromosome.included_corners.remove(&corner);
+                }
+                if !removed_point && corner.is_none() {
+                    // a terminal
+                    continue;
+                }
+                self.population[index] = Individual {
+                    chromosome,
+                    minimum_spanning_tree: None,
+                };
+                self.build_mst(index, BufferSelector::Population);
+                let weight = self.population[index]
+                    .minimum_spanning_tree
+                    .as_ref()
+                    .unwrap()
+                    .total_weight;
+                if weight <= mst.total_weight {
+                    continue 'search;
+                }
+                self.population[index] = original.clone();
+            }
+            break;
+        }
+    }
+
+    pub fn finalize(&mut self) {
+        self.build_msts();
+        self.remove_degree_two_steiner_points(0);
+        self.relocate_to_fermat_points(0, FINALIZE_ITERATIONS);
+    }
+
+    /// moves every free Steiner point of degree three in the tree of the
+    /// individual at `index` to the Fermat point of its neighbors, pass after
+    /// pass, until a pass gains less than `EPSILON` or `max_passes` are
+    /// done. Points whose Fermat point lies outside the Steiner region or
+    /// inside a solid obstacle stay put, even where a solid penalty would
+    /// make the move pay off. The moved points are only kept if the tree got
+    /// lighter.
+    fn relocate_to_fermat_points(&mut self, index: usize, max_passes: usize) {
+        let individual = &self.population[index];
+        let original_weight = individual.weight();
+        let mut mst = individual.minimum_spanning_tree.clone().unwrap();
+        let movable: Vec<NodeIndex> = mst
+            .graph
+            .node_indices()
+            .filter(|&node| {
+                individual
+                    .chromosome
+                    .steiner_points
+                    .contains(&to_graph(mst.graph[node]))
+            })
+            .collect();
+        let original_positions: Vec<Point> = movable.iter().map(|&node| mst.graph[node]).collect();
+        for _ in 0..max_passes {
+            let previous_weight = mst.total_weight;
+            for &node in &movable {
+                let neighbors: Vec<Point> =
+                    mst.graph.neighbors(node).map(|n| mst.graph[n]).collect();
+                if let [a, b, c] = neighbors[..] {
+                    let moved = fermat_point(a, b, c, EPSILON);
+                    if in_region(moved, self.config.steiner_region())
+                        && !self.problem.coordinates_in_solid_obstacle(moved)
+                    {
+                        mst.graph[node] = moved;
+                    }
+                }
+            }
+            for edge in mst.graph.edge_indices() {
+                let (from, to) = mst.graph.edge_endpoints(edge).unwrap();
+                mst.graph[edge] =
+                    self.compute_distance(to_graph(mst.graph[from]), to_graph(mst.graph[to]));
+            }
+            self.edge_evaluations += mst.graph.edge_count() as u64;
+            mst.total_weight = mst.graph.edge_weights().sum();
+            if previous_weight - mst.total_weight < EPSILON {
+                break;
+            }
+        }
+        if mst.total_weight < original_weight {
+            mst.fitness = self.config.fitness.evaluate(&mst, &self.problem);
+            let chromosome = &mut self.population[index].chromosome;
+            for &original in &original_positions {
+                chromosome.steiner_points_mut().shift_remove(&to_graph(original));
+            }
+            for &node in &movable {
+                chromosome.steiner_points_mut().insert(to_graph(mst.graph[node]));
+            }
+            self.population[index].minimum_spanning_tree = Some(mst);
+        }
+    }
+
+    pub fn new(
+        rng: R,
+        problem: SteinerProblem,
+        population_size: usize,
+        t1: usize,
+        t2: usize,
+        t3: usize,
+    ) -> Self {
+        let config = StobgaConfig {
+            population_size,
+            t1,
+            t2,
+            t3,
+            ..StobgaConfig::default()
+        };
+        Self::with_config(rng, problem, config)
+    }
+
+    /// sets up the initial population of t1, t2 and t3 individuals as
+    /// described by the config and fills it up through crossover. Panics on
+    /// an invalid config, see [StOBGA::try_with_config].
+    pub fn with_config(rng: R, problem: SteinerProblem, config: StobgaConfig) -> Self {
+        Self::try_with_config(rng, problem, config).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// like [StOBGA::with_config], but failing with
+    /// [SolverError::TournamentSize] if the tournament size is 0 or exceeds
+    /// the population size, and with [SolverError::InitFractions] for
+    /// initialization fractions that do not fit into the population.
+    pub fn try_with_config(
+        mut rng: R,
+        mut problem: SteinerProblem,
+        config: StobgaConfig,
+    ) -> Result<Self, SolverError> {
+        config.validate()?;
+        // a cached baseline may stem from a configuration with other edge costs
+        problem.terminal_mst_weight = OnceLock::new();
+        let population_size = config.population_size;
+        let (t1, t2, t3) = config.init_counts()?;
+        let region = config.steiner_region();
+        let mut population = vec![];
+        for _ in 0..t1 {
+            population.push(Individual {
+                chromosome: Chromosome {
+                    steiner_points: problem
+                        .centroids
+                        .iter()
+                        .filter(|&&p| in_region(p, region))
+                        .map(|&p| to_graph(p))
+                        .collect(),
+                    included_corners: Corners::new(),
+                    ..Default::default()
+                },
+                minimum_spanning_tree: Option::None,
+            });
+        }
+
+        let k = config.corner_count(&problem);
+        let all_corners = (0..k).collect::<Corners>();
+        let grid = match config.init_strategy {
+            InitStrategy::Random
+            | InitStrategy::Kmeans { .. }
+            | InitStrategy::CornersNearTerminals => None,
+            InitStrategy::Grid { nx, ny } => Some(
+                problem
+                    .grid_points(nx, ny)
+                    .into_iter()
+                    .filter(|&p| in_region(p, region))
+                    .collect::<Vec<_>>(),
+            ),
+        };
+        let mut cluster_centers = match config.init_strategy {
+            InitStrategy::Kmeans { k } => Some(
+                geometry::kmeans(&problem.terminals, k)
+                    .into_iter()
+                    .filter(|&p| !problem.coordinates_in_solid_obstacle(p) && in_region(p, region))
+                    .map(to_graph)
+                    .collect::<IndexSet<_>>(),
+            ),
+            _ => None,
+        };
+        let mut t2_random = t2;
+        if let Some(steiner_points) = cluster_centers.take() {
+            if t2 > 0 {
+                population.push(Individual {
+                    chromosome: Chromosome {
+                        steiner_points,
+                        included_corners: all_corners.clone(),
+                        ..Default::default()
+                    },
+                    minimum_spanning_tree: Option::None,
+                });
+                t2_random -= 1;
+            }
+        }
+
+        let corner_probabilities = (config.init_strategy == InitStrategy::CornersNearTerminals)
+            .then(|| problem.corner_probabilities(k));
+
+        // every random individual gets its own generator, seeded in order
+        // from `rng`, so that building them in parallel yields the same
+        // population as building them one after another.
+        let seeds: Vec<(bool, u64)> = (0..t2_random + t3)
+            .map(|i| (i < t2_random, rng.gen()))
+            .collect();
+        let seed_individual = |&(random_steiner_points, seed): &(bool, u64)| {
+            let mut rng = rand_pcg::Pcg32::seed_from_u64(seed);
+            if random_steiner_points {
+                Individual::random_steiner_points(&problem, k, grid.as_deref(), region, &mut rng)
+            } else {
+                Individual::random_corners(k, corner_probabilities.as_deref(), &mut rng)
+            }
+        };
+        let terminal_distances = problem.terminal_distances(&config);
+        let mut edge_evaluations = terminal_distances.len() as u64;
+        let terminal_hull = config
+            .hull_constraint
+            .then(|| geometry::convex_hull(&problem.terminals));
+        let seed_individual = |seed: &(bool, u64)| {
+            let mut individual = seed_individual(seed);
+            if let Some(hull) = &terminal_hull {
+                individual.chromosome.clamp_to_hull(hull);
+            }
+            individual
+        };
+        if config.parallel_init {
+            let mut seeded: Vec<Individual> = seeds.par_iter().map(seed_individual).collect();
+            edge_evaluations += seeded
+                .par_iter_mut()
+                .map_init(HashMap::new, |cache, individual| {
+                    let cached = cache.len();
+                    individual.minimum_spanning_tree = Some(problem.spanning_tree(
+                        &individual.chromosome,
+                        cache,
+                        &config,
+                        Some(&terminal_distances),
+                    ));
+                    (cache.len() - cached) as u64
+                })
+                .sum::<u64>();
+            population.append(&mut seeded);
+        } else {
+            population.extend(seeds.iter().map(seed_individual));
+        }
+        let evaluated = population
+            .iter()
+            .filter(|individual| individual.minimum_spanning_tree.is_some())
+            .count() as u64;
+
+        let mut stobga = StOBGA {
+            problem,
+            config,
+            population,
+            random_generator: rng,
+            current_generation: 0,
+            child_buffer: Vec::new(),
+            crossovers: Vec::new(),
+            edge_db: HashMap::new(),
+            terminal_distances,
+            terminal_hull,
+            function_evaluations: evaluated,
+            edge_evaluations,
+            phase_timings: PhaseTimings::default(),
+            operator_stats: OperatorStats::default(),
+            previous_operator_stats: OperatorStats::default(),
+            mutation_probabilities: [1.0 / 3.0; 3],
+            pareto: ParetoArchive::new(),
+            best_weight: INF,
+            best_found_generation: 0,
+            #[cfg(not(target_arch = "wasm32"))]
+            start_time: SystemTime::now(),
+        };
+        stobga.build_msts();
+        for _ in 0..(population_size - (t1 + t2 + t3)) {
+            let p1 = stobga.tournament_select(false);
+            let p2 = stobga.tournament_select(false);
+            stobga.crossover(p1, p2);
+            for i in 1..=stobga.children_per_crossover() {
+                stobga.mutate(stobga.child_buffer.len() - i);
+            }
+            // stobga.build_mst(stobga.child_buffer.len() - 1, BufferSelector::ChildBuffer);
+            // stobga.build_mst(stobga.child_buffer.len() - 2, BufferSelector::ChildBuffer);
+            if stobga.population.len() + stobga.child_buffer.len() >= population_size {
+                while stobga.population.len() + stobga.child_buffer.len() > population_size {
+                    stobga.child_buffer.pop();
+                }
+                break;
+            }
+        }
+        stobga.record_crossovers();
+        stobga.population.append(&mut stobga.child_buffer);
+        stobga.build_msts();
+        // only the generations are timed
+        stobga.phase_timings = PhaseTimings::default();
+        assert_eq!(stobga.population.len(), population_size);
+        Ok(stobga)
+    }
+
+    pub fn instance_to_svg(& self, index : usize) -> String {
+        let mut result = self.svg_obstacles();
+        result = format!("{}{}", result, self.svg_tree(index));
+        format!("{}{}</svg>", result, self.svg_terminals())
+    }
+
+    /// like [StOBGA::instance_to_svg], with the Delaunay
+    /// [centroids](SteinerProblem::centroids) drawn on top in purple.
+    pub fn instance_to_svg_with_centroids(&self, index: usize) -> String {
+        format!(
+            "{}{}{}{}</svg>",
+            self.svg_obstacles(),
+            self.svg_tree(index),
+            self.svg_centroids(),
+            self.svg_terminals()
+        )
+    }
+
+    /// the `n` best individuals with distinct chromosomes side by side,
+    /// `cols` per row, each drawn like [StOBGA::instance_to_svg] and labeled
+    /// with its weight. Individuals without a tree yet are left out.
+    pub fn population_grid_svg(&self, cols: usize, n: usize) -> String {
+        let cols = cols.max(1);
+        let mut ranked: Vec<usize> = (0..self.population.len())
+            .filter(|&index| self.population[index].minimum_spanning_tree.is_some())
+            .collect();
+        ranked.sort_by(|&i1, &i2| self.population[i1].fitness().total_cmp(&self.population[i2].fitness()));
+        let mut chosen: Vec<usize> = Vec::new();
+        for index in ranked {
+            if chosen.len() == n {
+                break;
+            }
+            let chromosome = &self.population[index].chromosome;
+            if chosen.iter().all(|&other| &self.population[other].chromosome != chromosome) {
+                chosen.push(index);
+            }
+        }
+        let scaling_factor = 1000.0;
+        let label_height = 0.1 * scaling_factor;
+        let bounds = &self.problem.bounds;
+        let cell_width = (bounds.max_x - bounds.min_x) * scaling_factor;
+        let cell_height = (bounds.max_y - bounds.min_y) * scaling_factor + label_height;
+        // the drawings start at x = 0 but their content only at min_x, and
+        // they are flipped vertically, so their content starts at y = 0
+        let offset_x = bounds.min_x * scaling_factor;
+        let rows = chosen.len().div_ceil(cols);
+        let mut result = format!(
+            "<svg width='{}px' height='{}px'>",
+            cols.min(chosen.len()) as f32 * cell_width,
+            rows as f32 * cell_height
+        );
+        for (cell, &index) in chosen.iter().enumerate() {
+            let x = (cell % cols) as f32 * cell_width - offset_x;
+            let y = (cell / cols) as f32 * cell_height;
+            let weight = self.population[index].minimum_spanning_tree.as_ref().unwrap().total_weight;
+            result = format!(
+                "{}<g transform='translate({},{})'>{}<text x='{}' y='{}' font-size='{}'>{}</text></g>",
+                result,
+                x,
+                y,
+                self.instance_to_svg(index),
+                offset_x,
+                cell_height - label_height * 0.2,
+                label_height * 0.7,
+                weight
+            );
+        }
+        format!("{}</svg>", result)
+    }
+
+    fn svg_centroids(&self) -> String {
+        let scaling_factor = 1000.0;
+        let move_y = self.problem.bounds.max_y*scaling_factor;
+        let mut result = String::new();
+        for centroid in self.problem.centroids.iter() {
+            result = format!("{} <circle cx='{}' cy='{}' r='6' fill='#9B59B6'/>", result, centroid.0*scaling_factor, -centroid.1*scaling_factor + move_y);
+        }
+        result
+    }
+
+    /// the opening svg tag and the obstacles.
+    fn svg_obstacles(&self) -> String {
+        let scaling_factor = 1000.0;
+        let move_y = self.problem.bounds.max_y*scaling_factor;
+        let mut result = format!("<svg width='{}px' height='{}px'>", self.problem.bounds.max_x*scaling_factor, self.problem.bounds.max_y*scaling_factor).to_string();
+        for obstacle in &self.problem.obstacles {
+            let mut svg = format!("<polygon style='fill:{}' points='", {
+                if obstacle.weight == INF {
+                    "#E86441"
+                } else {
+                    "#FFDD54"
+                }
+            }).to_string();
+            for corner in &obstacle.points {
+                svg = format!("{} {},{}", svg, corner.0*scaling_factor, -corner.1*scaling_factor + move_y);
+            }
+            svg = format!("{}'/>", svg);
+            result = format!("{} {}", result, svg);
+        }
+        result
+    }
+
+    /// the edges, Steiner points and included corners of an individual.
+    fn svg_tree(&self, index: usize) -> String {
+        let scaling_factor = 1000.0;
+        let move_y = self.problem.bounds.max_y*scaling_factor;
+        let instance = &self.population[index];
+        let mut result = String::new();
+        let graph = &instance.minimum_spanning_tree.as_ref().unwrap().graph;
+        for edge in graph.edge_references() {
+            let from = graph[edge.source()];
+            let to = graph[edge.target()];
+            // edges paying a penalty inside a weighted obstacle are dashed
+            let style = if self.problem.segment_crosses_weighted_obstacle(from, to) {
+                "stroke:#E0533A;stroke-width:2px;stroke-dasharray:20,10"
+            } else {
+                "stroke:black;stroke-width:2px"
+            };
+            result = format!("{}<line x1='{}' y1='{}' x2='{}' y2='{}' style='{}'/>", result, from.0*scaling_factor, -from.1*scaling_factor + move_y, to.0*scaling_factor, -to.1*scaling_factor + move_y, style);
+        }
+        for steiner_point in instance.chromosome.steiner_points.iter() {
+            result = format!("{} <circle cx='{}' cy='{}' r='10' fill='#59CDF7'/>", result, steiner_point.0*scaling_factor, -steiner_point.1*scaling_factor + move_y);
+        }
+        for corner in instance.chromosome.included_corners.iter() {
+            let steiner_point = self.problem.obstacle_corners[corner];
+            result = format!("{} <circle cx='{}' cy='{}' r='10' fill='grey'/>", result, steiner_point.0*scaling_factor, -steiner_point.1*scaling_factor + move_y);
+        }
+        result
+    }
+
+    fn svg_terminals(&self) -> String {
+        let scaling_factor = 1000.0;
+        let move_y = self.problem.bounds.max_y*scaling_factor;
+        let mut result = String::new();
+        for terminal in self.problem.terminals.iter() {
+            result = format!("{} <circle cx='{}' cy='{}' r='10' fill='black'/>", result, terminal.0*scaling_factor, -terminal.1*scaling_factor + move_y);
+        }
+        result
+    }
+
+    /// runs like [StOBGA::run] and draws every significant improvement of the
+    /// best individual as a frame of a single animated svg, which shows each
+    /// frame for half a second through SMIL `<animate>` elements and loops.
+    pub fn run_to_animated_svg(&mut self) -> String {
+        let mut termination = self.default_termination();
+        let mut frames = Vec::new();
+        let mut previous_best_weight = INF;
+        loop {
+            self.step();
+            let stop = termination.should_stop(self);
+            if stop {
+                self.finalize();
+            }
+            let best_weight = self.population[0].minimum_spanning_tree.as_ref().unwrap().total_weight;
+            if util::is_improvement_by_factor(previous_best_weight, best_weight, IMPROVEMENT_FACTOR) || stop {
+                previous_best_weight = best_weight;
+                frames.push(self.svg_tree(0));
+            }
+            if stop {
+                break;
+            }
+        }
+        let n = frames.len();
+        let mut result = self.svg_obstacles();
+        for (i, frame) in frames.iter().enumerate() {
+            let start = i as f32 / n as f32;
+            let (values, key_times) = if i + 1 < n {
+                ("hidden;visible;hidden", format!("0;{};{}", start, (i + 1) as f32 / n as f32))
+            } else {
+                ("hidden;visible", format!("0;{}", start))
+            };
+            result = format!(
+                "{}<g visibility='hidden'><animate attributeName='visibility' values='{}' keyTimes='{}' dur='{}s' calcMode='discrete' repeatCount='indefinite'/>{}</g>",
+                result, values, key_times, n as f32 * 0.5, frame
+            );
+        }
+        format!("{}{}</svg>", result, self.svg_terminals())
+    }
+
+    /// the seconds passed since the algorithm was set up. There is no system
+    /// clock on wasm, so the runtime is unknown there.
+    pub fn runtime(&self) -> Option<f32> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return SystemTime::now()
+            .duration_since(self.start_time)
+            .ok()
+            .map(|duration| duration.as_secs_f32());
+        #[cfg(target_arch = "wasm32")]
+        None
+    }
+
+    /// runs generations until none of the last `recession_duration`
+    /// generations improved significantly on the best individual, or until
+    /// `max_generations` have passed, then polishes the best individual.
+    pub fn run(&mut self) {
+        let mut termination = self.default_termination();
+        self.run_until(&mut termination);
+    }
+
+    /// stagnation for `recession_duration` generations, or `max_generations`.
+    fn default_termination(&self) -> Composite<R> {
+        let mut criteria: Vec<Box<dyn Termination<R>>> =
+            vec![Box::new(Stagnation::new(self.config.recession_duration))];
+        if let Some(max_generations) = self.config.max_generations {
+            criteria.push(Box::new(MaxGenerations(max_generations)));
+        }
+        Composite(criteria)
+    }
+
+    /// runs generations until `termination` asks to stop, then polishes the
+    /// best individual.
+    pub fn run_until<T: Termination<R>>(&mut self, termination: &mut T) {
+        self.run_until_with(termination, |_| {});
+    }
+
+    /// like [StOBGA::run_until], calling `on_generation` after every
+    /// generation, before the best individual is polished.
+    pub fn run_until_with<T: Termination<R>>(
+        &mut self,
+        termination: &mut T,
+        mut on_generation: impl FnMut(&Self),
+    ) {
+        loop {
+            self.step();
+            on_generation(self);
+            if termination.should_stop(self) {
+                break;
+            }
+        }
+        self.finalize();
+    }
+
+    /// the instance being solved.
+    pub fn problem(&self) -> &SteinerProblem {
+        &self.problem
+    }
+
+    /// the lowest weight of an individual with a built tree.
+    /// the generation after which the best individual last improved by
+    /// [IMPROVEMENT_FACTOR], judged like [Stagnation] does, 0 before the
+    /// first generation. So after [StOBGA::run] on a fresh solver, at least
+    /// `recession_duration` generations have passed since; smaller
+    /// improvements after it are not counted.
+    pub fn best_found_generation(&self) -> usize {
+        self.best_found_generation
+    }
+
+    /// the best individual of the population, by its fitness, as a
+    /// [Solution].
+    pub fn best_solution(&mut self) -> Solution {
+        self.build_msts();
+        let best = self
+            .population
+            .iter()
+            .min_by(|i1, i2| i1.fitness().total_cmp(&i2.fitness()))
+            .unwrap();
+        Solution::from_individual(best, &self.problem)
+    }
+
+    /// the index of the best (or, if `to_die`, the worst) of
+    /// `tournament_size` randomly drawn individuals, or of all of them while
+    /// the initial population is still smaller than that.
+    fn tournament_select(&mut self, to_die: bool) -> usize {
+        let candidates = rand::seq::index::sample(
+            &mut self.random_generator,
+            self.population.len(),
+            self.config.tournament_size.min(self.population.len()),
+        );
+        self.tournament_winner(candidates.into_iter(), to_die)
+    }
+
+    /// the best (or, if `to_die`, the worst) of the `candidates` by their
+    /// fitness. Among equally fit individuals the one with the lowest index wins, so the
+    /// outcome does not depend on the order the candidates were drawn in.
+    fn tournament_winner(&self, candidates: impl Iterator<Item = usize>, to_die: bool) -> usize {
+        candidates
+            .min_by(|&i1, &i2| {
+                let (w1, w2) = (self.population[i1].fitness(), self.population[i2].fitness());
+                let by_weight = if to_die {
+                    w2.total_cmp(&w1)
+                } else {
+                    w1.total_cmp(&w2)
+                };
+                by_weight.then(i1.cmp(&i2))
+            })
+            .unwrap()
+    }
+
+    pub fn step(&mut self) {
+        match self.config.replacement {
+            ReplacementStrategy::Generational => self.generational_replacement(),
+            ReplacementStrategy::SteadyState => self.steady_state_replacement(),
+        }
+        if let Some(threshold) = self.config.diversity_threshold {
+            if self.diversity() < threshold {
+                self.immigrate();
+            }
+        }
+        if let Some(floor) = self.config.adaptive_mutation {
+            self.adapt_mutation_probabilities(floor);
+        }
+        self.update_pareto_archive();
+        self.current_generation += 1;
+        if let Some(mst) = &self.population[0].minimum_spanning_tree {
+            if util::is_improvement_by_factor(self.best_weight, mst.total_weight, IMPROVEMENT_FACTOR) {
+                self.best_weight = mst.total_weight;
+                self.best_found_generation = self.current_generation;
+            }
+        }
+        assert_eq!(self.population.len(), self.config.population_size);
+        assert_eq!(self.child_buffer.len(), 0);
+    }
+
+    /// replaces a third of the population by the children of parents picked
+    /// through tournaments; the individuals to die are picked the same way.
+    fn generational_replacement(&mut self) {
+        // println!("population size {}", self.population.len());
+        let number_offspring = self.number_offspring();
+        let indices_to_recombine = self.timed(|t| &mut t.selection, |stobga| {
+            let mut indices_to_recombine = HashSet::new();
+            // large tournaments keep picking the same few winners, so after
+            // too many draws the rest compete among those not picked yet
+            let max_draws = 10 * number_offspring;
+            for draws in 0.. {
+                if indices_to_recombine.len() >= number_offspring {
+                    break;
+                }
+                let p1 = if draws < max_draws {
+                    stobga.tournament_select(false)
+                } else {
+                    let remaining = (0..stobga.population.len())
+                        .filter(|index| !indices_to_recombine.contains(index))
+                        .collect::<Vec<_>>();
+                    let candidates = rand::seq::index::sample(
+                        &mut stobga.random_generator,
+                        remaining.len(),
+                        stobga.config.tournament_size.min(remaining.len()),
+                    );
+                    stobga.tournament_winner(candidates.into_iter().map(|i| remaining[i]), false)
+                };
+                // let p2 = self.tournament_select(false);
+                indices_to_recombine.insert(p1);
+                // println!("{}", indices_to_recombine.len());
+            }
+            indices_to_recombine
+        });
+        let mut indices_to_recombine = indices_to_recombine.iter().sorted().collect::<Vec<_>>();
+        indices_to_recombine.shuffle(&mut self.random_generator);
+        let mut pair = Vec::new();
+        for &&index in indices_to_recombine.iter() {
+            if pair.len() == 0{
+                pair.push(index);
+            } else if pair.len() == 1{
+                self.timed(|t| &mut t.crossover, |stobga| stobga.crossover(pair[0], index));
+                pair.clear();
+            }
+            else {
+                unreachable!();
+            }
+        }
+        self.mutate_child_buffer();
+        let to_die = number_offspring / 2 * self.children_per_crossover();
+        self.timed(|t| &mut t.selection, |stobga| {
+            for _ in 0..to_die {
+                let index = stobga.tournament_select(true);
+                stobga.population.remove(index);
+            }
+        });
+        assert_eq!(self.child_buffer.len(), to_die);
+        self.population.append(&mut self.child_buffer);
+        self.build_msts();
+        self.timed(|t| &mut t.sorting, Self::sort_population);
+        // println!("{}", "leavin step now");
+    }
+
+    /// recombines a single pair of parents picked through tournaments and
+    /// lets the mutated children take the places of the worst individuals.
+    fn steady_state_replacement(&mut self) {
+        self.timed(|t| &mut t.sorting, Self::sort_population);
+        let (p1, p2) = self.timed(|t| &mut t.selection, |stobga| {
+            (stobga.tournament_select(false), stobga.tournament_select(false))
+        });
+        self.timed(|t| &mut t.crossover, |stobga| stobga.crossover(p1, p2));
+        self.mutate_child_buffer();
+        self.population.truncate(self.population.len() - self.child_buffer.len());
+        self.population.append(&mut self.child_buffer);
+        self.timed(|t| &mut t.sorting, Self::sort_population);
+    }
+
+    /// mutates every child in the child buffer once.
+    fn mutate_child_buffer(&mut self) {
+        self.timed(|t| &mut t.mutation, |stobga| {
+            for i in 0..stobga.child_buffer.len() {
+                stobga.mutate(i);
+            }
+        });
+        self.record_crossovers();
+    }
+
+    /// runs `f`, adding the time it took to the `phase` of the timings.
+    /// Trees built meanwhile count for the MST building phase instead.
+    fn timed<T>(
+        &mut self,
+        phase: fn(&mut PhaseTimings) -> &mut Duration,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let start = now();
+        let mst_building = self.phase_timings.mst_building;
+        let result = f(self);
+        if let Some(start) = start {
+            let nested = self.phase_timings.mst_building - mst_building;
+            *phase(&mut self.phase_timings) += start.elapsed().saturating_sub(nested);
+        }
+        result
+    }
+
+    /// where the time of the generations so far went, see [PhaseTimings].
+    pub fn phase_timings(&self) -> PhaseTimings {
+        self.phase_timings
+    }
+
+    /// offers every feasible individual to the Pareto archive.
+    fn update_pareto_archive(&mut self) {
+        for individual in &self.population {
+            let weight = individual.weight();
+            let steiner_count = individual.chromosome.steiner_points.len()
+                + individual.chromosome.included_corners.iter().count();
+            if weight < INF && self.pareto.accepts(weight, steiner_count) {
+                self.pareto
+                    .insert(Solution::from_individual(individual, &self.problem));
+            }
+        }
+    }
+
+    /// the chromosome and tree weight of every individual of the population
+    /// whose tree is built, which after [StOBGA::new] and every
+    /// [StOBGA::step] are all of them.
+    pub fn population_iter(&self) -> impl Iterator<Item = (&Chromosome, f32)> + '_ {
+        self.population.iter().filter_map(|individual| {
+            let mst = individual.minimum_spanning_tree.as_ref()?;
+            Some((&individual.chromosome, mst.total_weight))
+        })
+    }
+
+    /// the non-dominated trade-offs between weight and the amount of
+    /// Steiner points (and obstacle corners) found so far, by increasing
+    /// amount of Steiner points.
+    pub fn pareto_front(&self) -> Vec<Solution> {
+        self.pareto.solutions()
+    }
+
+    /// puts the tree of `solution`, e.g. one saved by an earlier run, into
+    /// the population in place of the worst individual, so the run can go on
+    /// improving it. Obstacle corners the problem does not have are dropped.
+    pub fn seed_from_solution(&mut self, solution: &Solution) {
+        self.build_msts();
+        let corner_count = self.problem.obstacle_corners.len();
+        let chromosome = Chromosome::new(
+            solution.steiner_points.iter().copied(),
+            solution
+                .included_corners
+                .iter()
+                .copied()
+                .filter(|&corner| corner < corner_count),
+        );
+        let worst = self
+            .population
+            .iter()
+            .position_max_by(|i1, i2| i1.fitness().total_cmp(&i2.fitness()))
+            .expect("the population is not empty");
+        self.population[worst] = Individual {
+            chromosome,
+            minimum_spanning_tree: None,
+        };
+        self.build_mst(worst, BufferSelector::Population);
+        self.sort_population();
+    }
+
+    fn sort_population(&mut self) {
+        self.population
+            .sort_unstable_by(|i1, i2| i1.fitness().total_cmp(&i2.fitness()));
+    }
+
+    /// replaces the worst quarter of the sorted population with individuals
+    /// holding random Steiner points and a random subset of the obstacle
+    /// corners.
+    fn immigrate(&mut self) {
+        let k = self.config.corner_count(&self.problem);
+        let n = self.problem.terminals.len();
+        let bounds = self.problem.sampling_bounds();
+        let x_dist = Uniform::new(bounds.min_x, bounds.max_x);
+        let y_dist = Uniform::new(bounds.min_y, bounds.max_y);
+        let immigrants = self.population.len() / 4;
+        let first = self.population.len() - immigrants;
+        for index in first..self.population.len() {
+            let rng = &mut self.random_generator;
+            let r = rng.gen_range(0..(n + k));
+            let region = self.config.steiner_region();
+            let steiner_points = (0..r)
+                .filter_map(|_| {
+                    (0..REPAIR_ATTEMPTS)
+                        .map(|_| (rng.sample(x_dist), rng.sample(y_dist)))
+                        .find(|&p| in_region(p, region))
+                })
+                .map(to_graph)
+                .collect();
+            let amount = rng.gen_range(0..(k + 1));
+            let included_corners = rand::seq::index::sample(rng, k, amount).into_iter().collect();
+            self.population[index] = Individual {
+                chromosome: Chromosome {
+                    steiner_points,
+                    included_corners,
+                    ..Default::default()
+                },
+                minimum_spanning_tree: None,
+            };
+        }
+        self.build_msts();
+        self.sort_population();
+    }
+
+    fn compute_distance(&self, from: OPoint, to: OPoint) -> f32 {
+        self.problem.edge_cost(from, to, &self.config)
+    }
+
+    fn build_mst(&mut self, index: usize, buffer : BufferSelector) {
+        let individual = match buffer {
+            BufferSelector::ChildBuffer => &mut self.child_buffer[index],
+            BufferSelector::Population => &mut self.population[index],
+        };
+        if let Some(hull) = &self.terminal_hull {
+            individual.chromosome.clamp_to_hull(hull);
+        }
+        let start = now();
+        let cached = self.edge_db.len();
+        let mst = self.problem.spanning_tree(
+            &individual.chromosome,
+            &mut self.edge_db,
+            &self.config,
+            Some(&self.terminal_distances),
+        );
+        self.edge_evaluations += (self.edge_db.len() - cached) as u64;
+        if let Some(start) = start {
+            self.phase_timings.mst_building += start.elapsed();
+        }
+        match buffer {
+            BufferSelector::ChildBuffer => self.child_buffer[index].minimum_spanning_tree = Some(mst),
+            BufferSelector::Population => self.population[index].minimum_spanning_tree = Some(mst),
+        }
+        self.function_evaluations += 1;
+    }
+
+    pub fn build_msts(&mut self) {
+        for index in 0..self.population.len() {
+            if self.population[index].minimum_spanning_tree.is_none() {
+                self.build_mst(index, BufferSelector::Population);
+            }
+        }
+    }
+}
+
+impl Individual {
+    /// an individual with the first `k` obstacle corners and a random amount of
+    /// Steiner points, placed uniformly within the bounds or, given a
+    /// `grid`, on distinct grid points. Random points outside the `region`
+    /// are resampled up to `REPAIR_ATTEMPTS` times. These are the t2
+    /// individuals of the initial population.
+    fn random_steiner_points(
+        problem: &SteinerProblem,
+        k: usize,
+        grid: Option<&[Point]>,
+        region: SteinerRegion,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let n = problem.terminals.len();
+        let bounds = problem.sampling_bounds();
+        let x_dist = Uniform::new(bounds.min_x, bounds.max_x);
+        let y_dist = Uniform::new(bounds.min_y, bounds.max_y);
+        let mut steiner_points = IndexSet::new();
+        let r = rng.gen_range(0..(n + k));
+        match grid {
+            None => {
+                for _ in 0..r {
+                    let point = (0..REPAIR_ATTEMPTS)
+                        .map(|_| (rng.sample(x_dist), rng.sample(y_dist)))
+                        .find(|&p| in_region(p, region));
+                    if let Some(point) = point {
+                        steiner_points.insert(to_graph(point));
+                    }
+                }
+            }
+            Some(grid) => {
+                for i in rand::seq::index::sample(rng, grid.len(), r.min(grid.len())) {
+                    steiner_points.insert(to_graph(grid[i]));
+                }
+            }
+        }
+        Individual {
+            chromosome: Chromosome {
+                steiner_points,
+                included_corners: (0..k).collect(),
+                ..Default::default()
+            },
+            minimum_spanning_tree: None,
+        }
+    }
+
+    /// an individual without Steiner points and with a random subset of the
+    /// first `k` obstacle corners, uniformly drawn or, given `probabilities`,
+    /// including every corner with its probability. These are the t3
+    /// individuals of the initial population.
+    fn random_corners(k: usize, probabilities: Option<&[f64]>, rng: &mut impl Rng) -> Self {
+        let included_corners = match probabilities {
+            Some(probabilities) => (0..k)
+                .filter(|&corner| rng.gen_bool(probabilities[corner]))
+                .collect(),
+            None => {
+                let amount = rng.gen_range(0..(k + 1));
+                rand::seq::index::sample(rng, k, amount).into_iter().collect()
+            }
+        };
+        Individual {
+            chromosome: Chromosome {
+                steiner_points: IndexSet::new(),
+                included_corners,
+                ..Default::default()
+            },
+            minimum_spanning_tree: None,
+        }
+    }
+
+    /// the total weight of the built minimum spanning tree.
+    fn weight(&self) -> f32 {
+        self.minimum_spanning_tree.as_ref().unwrap().total_weight
+    }
+
+    /// the fitness of the built minimum spanning tree, see
+    /// [StobgaConfig::fitness].
+    fn fitness(&self) -> f32 {
+        self.minimum_spanning_tree.as_ref().unwrap().fitness
+    }
+
+    fn mutation_remove_steiner<R: Rng>(&mut self, problem: &SteinerProblem, rng: &mut R) {
+        let mut candidate_steiner_points = Vec::new();
+
+        let graph = &self.minimum_spanning_tree.as_ref().unwrap().graph;
+        for steiner_point in self.chromosome.steiner_points.iter() {
+            let id = graph
+                .node_indices()
+                .find(|id| graph[*id].0 == *steiner_point.0 && graph[*id].1 == *steiner_point.1)
+                .unwrap();
+            let edges = graph.edges(id);
+            if edges.count() <= 2 {
+                candidate_steiner_points.push(*steiner_point);
+            }
+        }
+        let mut candidate_corners = Vec::new();
+        for index_corner in self.chromosome.included_corners.iter() {
+            let steiner_point = problem.obstacle_corners[index_corner];
+            let id = graph
+                .node_indices()
+                .find(|id| graph[*id].0 == steiner_point.0 && graph[*id].1 == steiner_point.1)
+                .unwrap();
+            let edges = graph.edges(id);
+            if edges.count() <= 2 {
+                candidate_corners.push(index_corner.clone());
+            }
+        }
+        match (candidate_steiner_points.len(), candidate_corners.len()) {
+            (0, 0) => {}
+            (0, n) => {
+                self.chromosome
+                    .included_corners
+                    .remove(&candidate_corners[if n > 1 { rng.gen_range(0..n) } else { 0 }]);
+            }
+            (n, 0) => {
+                self.chromosome
+                    .steiner_points
+                    .remove(&candidate_steiner_points[if n > 1 { rng.gen_range(0..n) } else { 0 }]);
+            }
+            (n, m) => {
+                if rng.gen_bool((n as f32 / m as f32).clamp(0.0, 1.0) as f64) {
+                    self.chromosome.steiner_points_mut().remove(
+                        &candidate_steiner_points[if n > 1 { rng.gen_range(0..n) } else { 0 }],
+                    );
+                } else {
+                    self.chromosome
+                        .included_corners
+                        .remove(&candidate_corners[if m > 1 { rng.gen_range(0..m) } else { 0 }]);
+                }
+            }
+        }
+        self.minimum_spanning_tree = None;
+    }
+
+    /// all triples of a node and two of its neighbors in the minimum spanning
+    /// tree, between whose edges the angle is less than `angle_threshold`.
+    fn add_steiner_candidates(
+        &self,
+        angle_threshold: f32,
+    ) -> Vec<(NodeIndex, NodeIndex, NodeIndex)> {
+        let mut candidates = Vec::new();
+        let graph = &self.minimum_spanning_tree.as_ref().unwrap().graph;
+        for i1 in graph.node_indices() {
+            let connections = graph.edges(i1);
+            for edge in connections.combinations(2) {
+                let i2 = edge[0].target();
+                let i3 = edge[1].target();
+                let angle = geometry::angle(graph[i1], graph[i2], graph[i3]);
+                if angle < angle_threshold {
+                    candidates.push((i1, i2, i3));
+                }
+            }
+        }
+        candidates
+    }
+
+    fn mutation_add_steiner<R: Rng>(
+        &mut self,
+        problem: &SteinerProblem,
+        rng: &mut R,
+        angle_threshold: f32,
+        region: SteinerRegion,
+    ) {
+        let candidates = self.add_steiner_candidates(angle_threshold);
+        let graph = &self.minimum_spanning_tree.as_ref().unwrap().graph;
+        if candidates.len() == 0 {
+            // add a random Steiner point, unless none of SAMPLE_ATTEMPTS
+            // samples is valid
+            let bounds = problem.sampling_bounds();
+            let (min_x, max_x) = (bounds.min_x, bounds.max_x);
+            let (min_y, max_y) = (bounds.min_y, bounds.max_y);
+            let new_steiner = (0..SAMPLE_ATTEMPTS)
+                .map(|_| (rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y)))
+                .find(|&p| {
+                    !problem.coordinates_in_solid_obstacle(p)
+                        && !problem.coordinates_on_terminal(p)
+                        && in_region(p, region)
+                });
+            if let Some(new_steiner) = new_steiner {
+                self.chromosome.steiner_points_mut().insert(to_graph(new_steiner));
+            }
+        } else {
+            let random_triple = candidates[if candidates.len() > 1 {
+                rng.gen_range(0..candidates.len())
+            } else {
+                0
+            }];
+            let p1 = graph[random_triple.0];
+            let p2 = graph[random_triple.1];
+            let p3 = graph[random_triple.2];
+            let p4 = geometry::fermat_point(p1, p2, p3, EPSILON);
+            if !problem.coordinates_in_solid_obstacle(p4)
+                && !problem.coordinates_on_terminal(p4)
+                && in_region(p4, region)
+                && self
+                    .chromosome
+                    .nearest_steiner_point(p4)
+                    .is_none_or(|(_, distance)| distance > 1e-2)
+            {
+                self.chromosome.steiner_points_mut().insert(to_graph(p4));
+            }
+        }
+        self.minimum_spanning_tree = None;
+    }
+
+    fn mutation_flip_move<R: Rng>(
+        &mut self,
+        problem: &SteinerProblem,
+        rng: &mut R,
+        generation: usize,
+        k: usize,
+        region: SteinerRegion,
+    ) {
+        let s = self.chromosome.steiner_points.len();
+        let p_gene = if s + k == 0 {
+            1.0
+        } else {
+            1.0 / ((s + k) as f32)
+        };
+        let m_range = problem.average_terminal_distance
+            * f32::max(1.0 - (generation as f32) / 1000.0, M_RANGE_MIN);
+        let mut to_remove = Vec::new();
+        let mut to_add = Vec::new();
+        for &steiner_point in self.chromosome.steiner_points.iter() {
+            if rng.gen_bool(p_gene as f64) {
+                // moves onto a terminal or out of the region are
+                // resampled, and given up on after MOVE_ATTEMPTS tries
+                for _ in 0..MOVE_ATTEMPTS {
+                    let x_sign = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+                    let y_sign = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+                    let moved = if m_range > M_RANGE_MIN {
+                        let dist = Uniform::new(M_RANGE_MIN, m_range);
+                        (
+                            OrderedFloat(*steiner_point.0 + dist.sample(rng) * x_sign),
+                            OrderedFloat(*steiner_point.1 + dist.sample(rng) * y_sign),
+                        )
+                    } else {
+                        (
+                            OrderedFloat(*steiner_point.0 + M_RANGE_MIN * x_sign),
+                            OrderedFloat(*steiner_point.1 + M_RANGE_MIN * y_sign),
+                        )
+                    };
+                    if !problem.coordinates_on_terminal(to_point(moved))
+                        && in_region(to_point(moved), region)
+                    {
+                        to_remove.push(steiner_point);
+                        to_add.push(moved);
+                        break;
+                    }
+                }
+            }
+        }
+        for point in to_remove {
+            self.chromosome.steiner_points_mut().remove(&point);
+        }
+        for point in to_add {
+            self.chromosome.steiner_points_mut().insert(point);
+        }
+        for i in 0..k {
+            if rng.gen_bool(p_gene as f64) {
+                if self.chromosome.included_corners.contains(&i) {
+                    self.chromosome.included_corners.remove(&i);
+                } else {
+                    self.chromosome.included_corners.insert(i);
+                }
+            }
+        }
+        self.minimum_spanning_tree = None
+    }
+}
+
+#[derive(Clone)]
+pub struct Obstacle {
+    pub weight: f32,
+    pub bounds: Bounds,
+    pub points: Vec<Point>,
+    /// an optional name, only carried along for the output
+    pub name: Option<String>,
+    /// a fixed cost paid every time an edge enters the obstacle, on top of
+    /// the weighted length inside of it
+    pub entry_cost: f32,
+    /// an optional slope making the obstacle direction dependent: moving
+    /// along the unit direction `d` inside of it costs `weight + d · slope`
+    /// per length instead of `weight`, but never less than zero
+    pub directional: Option<(f32, f32)>,
+    /// the edges of the polygon, see [geometry::polygon_edges]. Filled in
+    /// along with the bounds by [Obstacle::compute_bounds].
+    pub edges: Vec<(Point, Point)>,
+}
+
+impl std::fmt::Debug for Obstacle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Obstacle")
+            .field("weight", &self.weight)
+            .field("bounds", &self.bounds)
+            .field("points", &self.points)
+            .field("name", &self.name)
+            .field("entry_cost", &self.entry_cost)
+            .field("directional", &self.directional)
+            .field("edges", &self.edges)
+            .finish()
+    }
+}
+
+impl Obstacle {
+    pub fn new(weight: f32, points: Vec<Point>) -> Self {
+        Self {
+            weight,
+            points,
+            bounds: Bounds::default(),
+            name: None,
+            entry_cost: 0.0,
+            directional: None,
+            edges: Vec::new(),
+        }
+    }
+
+    /// the weight for crossing the obstacle in the (unit) `direction`,
+    /// which only matters for directional obstacles.
+    pub(crate) fn weight_along(&self, direction: Point) -> f32 {
+        match self.directional {
+            Some(slope) => (self.weight + direction.0 * slope.0 + direction.1 * slope.1).max(0.0),
+            None => self.weight,
+        }
+    }
+
+    /// whether `p` lies inside the obstacle. Points on its boundary do not.
+    pub fn contains(&self, p: Point) -> bool {
+        geometry::point_in_polygon(p.0, p.1, &self.points, &self.bounds)
+    }
+
+    /// whether the segment from `a` to `b` runs through the inside of the
+    /// obstacle. Running along or touching its boundary does not count.
+    pub fn intersects_segment(&self, a: Point, b: Point) -> bool {
+        geometry::intersection_length(a.0, a.1, b.0, b.1, &self.points, &self.bounds) > 0.0
+    }
+
+    pub fn compute_bounds(mut self) -> Obstacle {
+        self.bounds = Bounds::of_points(&self.points);
+        self.edges = geometry::polygon_edges(&self.points).collect();
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashSet, time::Instant, fmt::Binary};
+
+    use crate::{
+        *, geometry::{intersection_length, middle, point_in_polygon, segment_polygon_intersection}, graph::Graph,
+    };
+    use itertools::Itertools;
+    use petgraph::{data::FromElements, prelude::UnGraph};
+    use rand::{Rng, SeedableRng};
+
+    /// builds a problem from a grid of unit cells, drawn top row first:
+    /// `T` is a terminal in the center of its cell, `#` is a cell covered
+    /// by a solid obstacle and `.` is free space.
+    fn problem_from_ascii(grid: &str) -> SteinerProblem {
+        let rows = grid.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>();
+        let mut terminals = Vec::new();
+        let mut obstacles = Vec::new();
+        for (r, row) in rows.iter().enumerate() {
+            let y = (rows.len() - 1 - r) as f32;
+            for (c, cell) in row.chars().enumerate() {
+                let x = c as f32;
+                match cell {
+                    'T' => terminals.push((x + 0.5, y + 0.5)),
+                    '#' => {
+                        let cell = vec![(x, y), (x + 1.0, y), (x + 1.0, y + 1.0), (x, y + 1.0)];
+                        obstacles.push(Obstacle::new(INF, cell).compute_bounds());
+                    }
+                    '.' => {}
+                    other => panic!("unexpected cell {:?}", other),
+                }
+            }
+        }
+        SteinerProblem::new(terminals, obstacles)
+    }
+
+    /// the algorithm set up with a population of 50 and seed 0, for tests
+    /// that need a solver but not the full population.
+    fn small_stobga(problem: SteinerProblem) -> StOBGA<rand_pcg::Pcg32> {
+        let config = StobgaConfig {
+            population_size: 50,
+            t2: 5,
+            t3: 5,
+            ..StobgaConfig::default()
+        };
+        StOBGA::with_config(rand_pcg::Pcg32::seed_from_u64(0), problem, config)
+    }
+
+    #[test]
+    fn test_geometry() {
+        assert_eq!(
+            crate::geometry::point_in_polygon(
+                0.0,
+                0.0,
+                &[(-1.0, -1.0), (1.0, 1.0), (0.0, 2.0)],
+                &geometry::Bounds {
+                    min_x: -1.0,
+                    max_x: 1.0,
+                    min_y: -1.0,
+                    max_y: 2.0
+                }
+            ),
+            false
+        )
+    }
+
+    #[test]
+    fn test_geometry2() {
+        assert_eq!(
+            crate::geometry::segment_polygon_intersection(
+                0.0,
+                0.0,
+                2.0,
+                0.0,
+                &[(1.0, 0.0), (1.0, -1.0), (-1.0, -1.0)],
+                true
+            ),
+            vec![(1.0, 0.0)]
+        );
+        assert_eq!(
+            crate::geometry::intersection_length(
+                0.0,
+                0.0,
+                2.0,
+                0.0,
+                &[(1.0, 0.0), (1.0, -1.0), (-1.0, -1.0)],
+                &geometry::Bounds {
+                    min_x: -1.0,
+                    max_x: 1.0,
+                    min_y: -1.0,
+                    max_y: 0.0
+                }
+            ),
+            0.0
+        );
+    }
+
+    // #[test]
+    // fn test_geometry3() {
+    //     assert_eq!(
+    //         crate::geometry::segment_polygon_intersection(
+    //             0.0,
+    //             0.0,
+    //             1.0,
+    //             1.0,
+    //             &[(0.0, 0.0), (1.0, 1.0), (1.0, -1.0)],
+    //             true
+    //         ),
+    //         Vec::new()
+    //     )
+    // }
+
+    #[test]
+    fn test_geometry4() {
+        assert_eq!(
+            crate::geometry::intersection_length(
+                3.0,
+                1.0,
+                4.0,
+                5.0,
+                &[(0.0, 0.0), (3.0, 1.0), (4.0, 5.0)],
+                &geometry::Bounds {
+                    min_x: 0.0,
+                    max_x: 4.0,
+                    min_y: 0.0,
+                    max_y: 5.0
+                }
+            ),
+            0.0
+        )
+    }
+
+    #[test]
+    fn instance_five_issue() {
+        // x = 0.3
+        let steiner_points = [
+            (0.39435774, 0.36414573), 
+            (0.478367, 0.45599815), 
+            (0.48510268, 0.82256573), 
+            (0.5242697, 0.7148127), 
+            
+            (0.09365932, 0.16696312),
+            // (0.09824701, 0.16467005), 
+            (0.10451312, 0.3484062), 
+            (0.2153477, 0.84840983), 
+            // (0.545881, 0.718454), 
+            ].iter().map(|&a|to_graph(a)).collect::<IndexSet<_>>();
+        let terminals = vec![
+            (0.644,0.242),
+            (0.24,0.386),
+            (0.048,0.39),
+            (0.152,0.15000000000000002),
+            (0.654,0.698),
+            (0.526,0.87),
+            (0.156,0.85),
+            (0.43,0.5900000000000001),
+            (0.91,0.72),
+            (0.88,0.634),
+            (0.728,0.406),
+        ];
+        let mut obstacles = vec![
+        Obstacle::new(5.0, vec![
+            (0.098,0.9),
+            (0.21,0.902),
+            (0.204,0.488),
+            (0.094,0.488),
+        ]).compute_bounds(),
+        Obstacle::new(5.0, vec![
+            (0.602,0.81),
+            (0.578,0.6),
+            (0.766,0.466),
+            (0.912,0.704),
+            (0.72,0.622),
+            (0.718,0.834)]).compute_bounds(),
+        Obstacle::new(5.0,vec![
+            (0.45,0.206),
+            (0.512,0.414),
+            (0.614,0.408),
+            (0.732,0.39),
+            (0.84,0.398),
+            (0.85,0.2),
+            (0.644,0.274)]).compute_bounds(),
+        Obstacle::new(5.0,vec![
+            (0.1,0.304),
+            (0.344,0.112),
+            (0.096,0.098),
+        ]).compute_bounds()
+        ];
+        let obstacle = Obstacle::new(5.0, vec![
+            (0.602,0.81),
+            (0.578,0.6),
+            (0.766,0.466),
+            (0.912,0.704),
+            (0.72,0.622),
+            (0.718,0.834)]).compute_bounds();
+        let rng = rand_pcg::Pcg32::seed_from_u64(2);
+        let included_corners = [
+            7, 
+            10, 
+            17
+            ].into_iter().collect();
+        let instance = SteinerProblem::new(terminals, obstacles);
+        let chromosome = Chromosome {
+            steiner_points,
+            included_corners,
+            ..Default::default()
+        };
+        
+        assert!(geometry::point_in_polygon(0.721041977,0.599999964, &obstacle.points, &obstacle.bounds));
+        assert!(!geometry::point_in_polygon(0.7965147, 0.48967615, &obstacle.points, &obstacle.bounds));
+        assert!(geometry::point_in_polygon(0.622285664, 0.703999758, &obstacle.points, &obstacle.bounds));
+        assert!(!geometry::point_in_polygon(0.545881, 0.718454, &obstacle.points, &obstacle.bounds));
+        assert!(geometry::intersection_length(0.654, 0.698, 0.545881, 0.718454, &obstacle.points, &obstacle.bounds) > 0.0);
+        assert!(geometry::intersection_length(0.545881, 0.718454,0.654, 0.698, &obstacle.points, &obstacle.bounds) > 0.0);
+        assert!(geometry::intersection_length(0.7965147, 0.48967615,0.654, 0.698, &obstacle.points, &obstacle.bounds) > 0.0);
+        let mut stobga = StOBGA::new(rng,instance, 500, 0, 500, 0);
+        
+        stobga.child_buffer = vec![Individual{chromosome, minimum_spanning_tree:None}];
+        stobga.build_mst(0, BufferSelector::ChildBuffer);
+        stobga.population[0] = stobga.child_buffer[0].clone();
+        println!("{}\n\n", stobga.instance_to_svg(0));
+        stobga.mutate_remove_steiner(0);
+        stobga.population[0] = stobga.child_buffer[0].clone();
+        stobga.build_mst(0, BufferSelector::ChildBuffer);
+        println!("{}\n\n", stobga.instance_to_svg(0));
+        // println!("{}",stobga.population[0].minimum_spanning_tree.as_ref().unwrap().total_weight);
+        // println!("{}",stobga.instance_to_svg(0));
+    }
+
+    #[test]
+    fn test_geometry6() {
+        let middle = middle(3.0, 1.0, 4.0, 5.0);
+        assert!(!point_in_polygon(
+            middle.0,
+            middle.1,
+            &[(0.0, 0.0), (3.0, 1.0), (4.0, 5.0)],
+            &geometry::Bounds {
+                min_x: 0.0,
+                max_x: 4.0,
+                min_y: 0.0,
+                max_y: 5.0
+            }
+        ))
+    }
+
+    #[test]
+    fn test_geometry7() {
+        let middle = middle(0.0, 0.0, 4.0, 5.0);
+        assert!(!point_in_polygon(
+            middle.0,
+            middle.1,
+            &[(0.0, 0.0), (3.0, 1.0), (4.0, 5.0)],
+            &geometry::Bounds {
+                min_x: 0.0,
+                max_x: 4.0,
+                min_y: 0.0,
+                max_y: 5.0
+            }
+        ))
+    }
+
+    #[test]
+    fn test_geometry8() {
+        let middle = middle(0.0, 0.0, 3.0, 1.0);
+        assert!(!point_in_polygon(
+            middle.0,
+            middle.1,
+            &[(0.0, 0.0), (3.0, 1.0), (4.0, 5.0)],
+            &geometry::Bounds {
+                min_x: 0.0,
+                max_x: 4.0,
+                min_y: 0.0,
+                max_y: 5.0
+            }
+        ))
+    }
+
+    #[test]
+    fn test_geometry9() {
+        assert_eq!(
+            crate::geometry::intersection_length(
+                0.0,
+                1.0,
+                1.0,
+                1.0,
+                &[(0.0, 0.0), (1.0, 0.0), (0.5, -1.0)],
+                &geometry::Bounds {
+                    min_x: 0.0,
+                    max_x: 1.0,
+                    min_y: -1.0,
+                    max_y: 0.0
+                }
+            ),
+            0.0
+        )
+    }
+
+    #[test]
+    fn test_geometry10() {
+        assert!(
+            crate::geometry::intersection_length(
+                0.845641974,
+                0.904959172,
+                0.753467217,
+                0.42431886,
+                &[
+                    (0.796, 0.898),
+                    (0.804, 0.784),
+                    (0.906, 0.792),
+                    (0.908, 0.886),
+                ],
+                &geometry::Bounds {
+                    min_x: 0.0,
+                    max_x: 1.0,
+                    min_y: 0.0,
+                    max_y: 1.0
+                }
+            ) > 0.0
+        )
+    }
+
+    #[test]
+    fn test_geometry11() {
+        println!(
+            "{}",
+            crate::geometry::intersection_length(
+                0.936640447,
+                0.706594727,
+                0.753467217,
+                0.42431886,
+                &[
+                    (0.784, 0.522),
+                    (0.798, 0.44799999999999995),
+                    (0.906, 0.45199999999999996),
+                    (0.9, 0.534),
+                ],
+                &geometry::Bounds {
+                    min_x: 0.0,
+                    max_x: 1.0,
+                    min_y: 0.0,
+                    max_y: 1.0
+                }
+            )
+        );
+        assert!(
+            crate::geometry::intersection_length(
+                0.936640447,
+                0.706594727,
+                0.753467217,
+                0.42431886,
+                &[
+                    (0.784, 0.522),
+                    (0.798, 0.44799999999999995),
+                    (0.906, 0.45199999999999996),
+                    (0.9, 0.534),
+                ],
+                &geometry::Bounds {
+                    min_x: 0.0,
+                    max_x: 1.0,
+                    min_y: 0.0,
+                    max_y: 1.0
+                }
+            ) > 0.0
+        )
+    }
+
+    #[test]
+    fn using_petgraph() {
+        let mut graph = petgraph::Graph::new_undirected();
+        let i1 = graph.add_node((1.0, 1.0));
+        let i2 = graph.add_node((2.0, 2.0));
+        graph.add_edge(i1, i2, 1.0);
+        let g2 = UnGraph::<_, _>::from_elements(petgraph::algo::min_spanning_tree(&graph));
+        assert!(g2.edge_weights().sum::<f32>() == 1.0)
+    }
+
+    #[test]
+    fn seeding_actually_makes_rand_reproducable() {
+        let mut rng = rand_pcg::Pcg32::seed_from_u64(0);
+        assert_eq!(rng.gen::<u64>(), 18195738587432868099);
+        let mut rng1 = rand_pcg::Pcg32::seed_from_u64(0);
+        assert_eq!(rng1.gen::<u64>(), 18195738587432868099);
+    }
+
+    #[test]
+    fn hashing_edges() {
+        let e1 = graph::Edge {
+            start: util::to_graph((0.0, 0.0)),
+            end: util::to_graph((1.0, 1.0)),
+        };
+        let e2 = graph::Edge {
+            end: util::to_graph((0.0, 0.0)),
+            start: util::to_graph((1.0, 1.0)),
+        };
+        let mut set = HashSet::new();
+        set.insert(e1);
+        set.insert(e2);
+        assert!(set.len() == 1);
+    }
+
+    #[test]
+    fn making_a_graph() {
+        let mut graph = graph::Graph::new();
+        graph.add_edge_from_points((0.0, 0.0), (1.0, 1.0), 1.0);
+        graph.add_edge_from_points((2.0, 0.0), (1.0, 1.0), 1.0);
+        graph.add_edge_from_points((0.0, 0.0), (1.0, 0.0), 1.0);
+        println!("{:?}", graph.edges_connected_to_point((1.0, 1.0)));
+    }
+
+    #[test]
+    fn trivial_mst() {
+        let mut graph = Graph::new();
+        graph.add_edge_from_points((0.0, 0.0), (0.0, 1.0), 1.0);
+        graph.add_edge_from_points((1.0, 1.0), (0.0, 1.0), 1.0);
+        let mst = graph.minimum_spanning_tree();
+        assert_eq!(mst.nodes.len(), 3);
+        assert_eq!(mst.edges.len(), 2);
+    }
+
+    #[test]
+    fn advanced_mst() {
+        let mut graph = Graph::new();
+        graph.add_edge_from_points((0.0, 0.0), (0.0, 1.0), 1.0);
+        graph.add_edge_from_points((0.0, 0.0), (1.0, 1.0), 2.0);
+        graph.add_edge_from_points((0.0, 0.0), (1.0, 0.0), 3.0);
+        graph.add_edge_from_points((1.0, 1.0), (0.0, 1.0), 4.0);
+        graph.add_edge_from_points((1.0, 1.0), (1.0, 0.0), 5.0);
+        graph.add_edge_from_points((1.0, 0.0), (0.0, 1.0), 6.0);
+        let mst = graph.minimum_spanning_tree();
+        assert_eq!(mst.nodes.len(), 4);
+        assert_eq!(mst.edges.len(), 3);
+        println!("{:?}", mst);
+        assert_eq!(mst.edges.values().sum::<f32>(), 6.0);
+        assert_eq!(graph.mst_kruskal().edges.values().sum::<f32>(), 6.0);
+    }
+
+    // #[test]
+    // fn build_binary_corners() {
+    //     let mut corners = crate::corners::BinaryCorners::new();
+    //     corners.insert(3);
+    //     corners.insert(4);
+    //     corners.insert(9);
+    //     assert_eq!(corners.iter().collect_vec(), vec![3, 4, 9])
+    // }
+
+    #[test]
+    // fn testing_binary_corners() {
+    //     let mut corners = (0..3).collect::<BinaryCorners>();
+    //     assert_eq!(corners.included, 7);
+    //     corners.remove(&0);
+    //     corners.remove(&1);
+    //     assert_eq!(corners.included, 4);
+    // }
+
+    #[test]
+    fn problematic_intersection() {
+        let obstacle = problematic_obstacle();
+        let start = (0.182, 0.126);
+        let end = (0.31, 0.018);
+        let distance = intersection_length(
+            start.0,
+            start.1,
+            end.0,
+            end.1,
+            &obstacle.points,
+            &obstacle.bounds,
+        );
+        assert_eq!(distance, euclidean_distance(start, end));
+    }
+
+    fn problematic_obstacle() -> Obstacle {
+        Obstacle {
+            weight: 4.0,
+            bounds: Bounds::default(),
+            name: None,
+            entry_cost: 0.0,
+            directional: None,
+            points: vec![
+                (0.116, 0.39),
+                (0.096, 0.29),
+                (0.084, 0.206),
+                (0.104, 0.048),
+                (0.31, 0.018),
+                (0.542, 0.072),
+                (0.5, 0.192),
+                (0.338, 0.144),
+                (0.256, 0.13),
+                (0.208, 0.158),
+                (0.208, 0.27),
+            ],
+            edges: Vec::new(),
+        }
+        .compute_bounds()
+    }
+
+    #[test]
+    fn problematic_lengths() {
+        let obstacle1 = Obstacle {
+            weight: INF,
+            bounds: Bounds::default(),
+            name: None,
+            entry_cost: 0.0,
+            directional: None,
+            edges: Vec::new(),
+            points: vec![
+                (0.83, 1.33),
+                (2.7, 1.19),
+                (0.91, 0.36),
+                (8.16, 1.31),
+                (6.43, 3.06),
+            ],
+        }
+        .compute_bounds();
+
+        let obstacle2 = Obstacle {
+            weight: INF,
+            bounds: Bounds::default(),
+            name: None,
+            entry_cost: 0.0,
+            directional: None,
+            edges: Vec::new(),
+            points: vec![(0.56, 1.27), (2.16, 1.09), (0.56, 0.33), (1.14, 0.88)],
+        }
+        .compute_bounds();
+
+        let obstacle3 = Obstacle {
+            weight: INF,
+            bounds: Bounds::default(),
+            name: None,
+            entry_cost: 0.0,
+            directional: None,
+            edges: Vec::new(),
+            points: vec![(0.19, 1.21), (0.82, 0.86), (0.18, 0.32)],
+        }
+        .compute_bounds();
+
+        let steiner1 = (0.56, 0.33);
+        let steiner2 = (0.82, 0.86);
+        let steiner3 = (0.56, 1.27);
+
+        let terminal1 = (2.89, 0.25);
+        let terminal2 = (2.43, 2.08);
+
+        let d1 = euclidean_distance(steiner1, steiner2);
+        let d2 = euclidean_distance(steiner2, steiner3);
+        let d3 = euclidean_distance(terminal1, steiner1);
+        let d4 = euclidean_distance(terminal2, steiner2);
+
+        let convenience = |v1: (f32, f32), v2: (f32, f32), p: Obstacle| {
+            geometry::intersection_length(v1.0, v1.1, v2.0, v2.1, &p.points, &p.bounds)
+        };
+        assert_eq!(convenience(steiner1, steiner2, obstacle1), 0.0);
+        assert_eq!(convenience(steiner1, steiner2, obstacle2), 0.0);
+        assert_eq!(convenience(steiner1, steiner2, obstacle3), 0.0);
+        // assert_eq!(geometry::intersection_length(steiner1.0, steiner1.1, steiner2.0, steiner2.1, &obstacle3.points, &obstacle3.bounds), 0.0);
+        // assert_eq!(d1+d2+d3+d4,0.0);
+    }
+
+    #[test]
+    fn wrapping_an_obstacle() {
+        let obstacle = wrapped_obstacle();
+        for i in 0..6 {
+            let a = obstacle.points[i];
+            let b = obstacle.points[i+1];
+            println!("i is {}", i);
+            assert_eq!(intersection_length(a.0,a.1, b.0,b.1, &obstacle.points, &obstacle.bounds), 0.0);
+        }
+        let a = obstacle.points[7];
+        let b = obstacle.points[0];
+        assert_eq!(intersection_length(a.0,a.1, b.0,b.1, &obstacle.points, &obstacle.bounds), 0.0);
+    }
+
+    fn wrapped_obstacle() -> Obstacle {
+        Obstacle {
+            points: 
+            vec![
+                (0.168,0.63),
+                (0.168,0.606),
+                (0.188,0.5840000000000001),
+                (0.226,0.5920000000000001),
+                (0.336,0.614),
+                (0.392,0.766),
+                (0.32,0.758),
+                (0.244,0.69),
+            ],
+            weight: 9999999.0,
+            bounds: Bounds::default(),
+            name: None,
+            entry_cost: 0.0,
+            directional: None,
+            edges: Vec::new(),
+        }.compute_bounds()
+    }
+
+    #[test]
+    fn coincident_points_make_no_zero_length_edges() {
+        let terminals = vec![(0.0, 0.0), (1.0, 0.0), (0.5, 1.0)];
+        let problem = SteinerProblem::new(terminals, vec![]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 0, 500, 0);
+        let chromosome = Chromosome {
+            steiner_points: [(0.0, 0.0), (0.5, 0.3)]
+                .iter()
+                .map(|&p| to_graph(p))
+                .collect(),
+            included_corners: Corners::new(),
+            ..Default::default()
+        };
+        stobga.child_buffer = vec![Individual {
+            chromosome,
+            minimum_spanning_tree: None,
+        }];
+        stobga.build_mst(0, BufferSelector::ChildBuffer);
+        let mst = stobga.child_buffer[0].minimum_spanning_tree.as_ref().unwrap();
+        assert_eq!(mst.graph.node_count(), 4);
+        assert!(mst.graph.edge_weights().all(|&w| w > 0.0));
+    }
+
+    #[test]
+    fn crossing_segments() {
+        let crossing = geometry::segment_intersection((0.0, 0.0), (1.0, 1.0), (0.0, 1.0), (1.0, 0.0));
+        assert_eq!(crossing, Some((0.5, 0.5)));
+        let parallel = geometry::segment_intersection((0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0));
+        assert_eq!(parallel, None);
+        let touching = geometry::segment_intersection((0.0, 0.0), (1.0, 0.0), (1.0, 0.0), (2.0, 0.0));
+        assert_eq!(touching, Some((1.0, 0.0)));
+        let overlapping = geometry::segment_intersection((0.0, 0.0), (1.0, 0.0), (0.5, 0.0), (2.0, 0.0));
+        assert_eq!(overlapping, None);
+    }
+
+    #[test]
+    fn exact_orientation() {
+        use geometry::{orientation, Orientation};
+        // a naive f32 cross product misjudges these nearly collinear points
+        let a = (0.5, 0.5);
+        let b = (12.0, 12.0);
+        assert_eq!(orientation(a, b, (24.0, 24.0)), Orientation::Collinear);
+        let above = (24.0, f32::from_bits(24.0f32.to_bits() + 1));
+        assert_eq!(orientation(a, b, above), Orientation::CounterClockwise);
+        assert_eq!(orientation(b, above, a), Orientation::CounterClockwise);
+        assert_eq!(orientation(b, a, above), Orientation::Clockwise);
+    }
+
+    #[test]
+    fn points_on_the_boundary_are_outside() {
+        let triangle = [(0.0, 0.0), (0.3, 0.3), (0.0, 0.9)];
+        let bounds = Bounds::default();
+        // exactly on an edge, on a vertex and strictly inside
+        assert!(!point_in_polygon(0.1, 0.1, &triangle, &bounds));
+        assert!(!point_in_polygon(0.3, 0.3, &triangle, &bounds));
+        assert!(point_in_polygon(0.1, 0.3, &triangle, &bounds));
+        // the classification does not depend on the polygon's orientation
+        let reversed = triangle.iter().rev().copied().collect::<Vec<_>>();
+        assert!(!point_in_polygon(0.1, 0.1, &reversed, &bounds));
+        assert!(point_in_polygon(0.1, 0.3, &reversed, &bounds));
+    }
+
+    #[test]
+    fn solving_json_to_svg() {
+        let svg = wasm::solve_to_svg(
+            "[[0.0, 0.0], [1.0, 0.0], [0.5, 1.0]]",
+            r#"[{"weight": 5.0, "points": [[0.4, 0.2], [0.6, 0.2], [0.5, 0.4]]}]"#,
+            0,
+            2,
+        );
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(wasm::solve_to_svg("[[0.0, 0.0]]", "[]", 0, 2), "");
+    }
+
+    #[test]
+    fn solving_through_ffi() {
+        use ffi::*;
+        let terminals = [0.0, 0.0, 1.0, 0.0, 0.5, 1.0, 0.5, 0.5];
+        let vertices = [0.4, 0.1, 0.6, 0.1, 0.5, 0.3];
+        let counts = [3];
+        let weights = [f32::INFINITY];
+        unsafe {
+            let handle = stobga_new(
+                terminals.as_ptr(),
+                4,
+                vertices.as_ptr(),
+                counts.as_ptr(),
+                weights.as_ptr(),
+                1,
+                0,
+            );
+            assert!(!handle.is_null());
+            let weight = stobga_run(handle, 2);
+            assert!(weight > 0.0 && weight < INF);
+            let edge_count = stobga_edges(handle, std::ptr::null_mut(), 0);
+            assert!(edge_count >= 3);
+            let mut buffer = vec![f32::NAN; 4 * edge_count];
+            assert_eq!(stobga_edges(handle, buffer.as_mut_ptr(), edge_count), edge_count);
+            assert!(buffer.iter().all(|c| c.is_finite()));
+            stobga_free(handle);
+            let null = std::ptr::null();
+            assert!(stobga_new(terminals.as_ptr(), 1, null, std::ptr::null(), null, 0, 0).is_null());
+            assert!(stobga_run(std::ptr::null_mut(), 1).is_nan());
+            // a line is no obstacle
+            let line = [2];
+            let handle = stobga_new(
+                terminals.as_ptr(),
+                4,
+                vertices.as_ptr(),
+                line.as_ptr(),
+                weights.as_ptr(),
+                1,
+                0,
+            );
+            assert!(handle.is_null());
+            for weight in [f32::NAN, f32::NEG_INFINITY] {
+                let weights = [weight];
+                let handle = stobga_new(
+                    terminals.as_ptr(),
+                    4,
+                    vertices.as_ptr(),
+                    counts.as_ptr(),
+                    weights.as_ptr(),
+                    1,
+                    0,
+                );
+                assert!(handle.is_null());
+            }
+        }
+    }
+
+    #[test]
+    fn solving_a_batch() {
+        let problems = vec![
+            SteinerProblem::new(vec![(0.0, 0.0), (1.0, 0.0), (0.5, 1.0)], vec![]),
+            SteinerProblem::new(vec![(0.0, 0.0), (1.0, 1.0), (0.0, 1.0), (1.0, 0.0)], vec![]),
+            SteinerProblem::new(
+                vec![(0.0, 0.0), (1.0, 0.0), (0.5, 1.0)],
+                vec![Obstacle::new(INF, vec![(0.4, 0.1), (0.6, 0.1), (0.5, 0.3)]).compute_bounds()],
+            ),
+        ];
+        let config = StobgaConfig {
+            max_generations: Some(2),
+            ..StobgaConfig::default()
+        };
+        let solutions = batch::solve_batch(problems, &config, &[0, 1]);
+        assert_eq!(solutions.len(), 3);
+        for solution in solutions {
+            assert!(solution.feasible);
+            assert!(solution.total_weight > 0.0);
+        }
+    }
+
+    #[test]
+    fn diffing_solutions() {
+        let solution = Solution {
+            steiner_points: vec![(0.5, 0.3)],
+            included_corners: vec![2],
+            edges: vec![
+                ((0.0, 0.0), (0.5, 0.3)),
+                ((1.0, 0.0), (0.5, 0.3)),
+                ((0.5, 1.0), (0.5, 0.3)),
+            ],
+            total_weight: 1.9,
+            feasible: true,
+        };
+        assert!(solution.diff(&solution.clone()).is_empty());
+        let mut other = solution.clone();
+        other.steiner_points.push((0.5, 0.8));
+        let diff = solution.diff(&other);
+        assert_eq!(diff.added_steiner_points, vec![(0.5, 0.8)]);
+        assert!(diff.removed_steiner_points.is_empty());
+        assert!(diff.added_corners.is_empty() && diff.removed_corners.is_empty());
+        assert!(diff.added_edges.is_empty() && diff.removed_edges.is_empty());
+        assert_eq!(diff.weight_delta, 0.0);
+    }
+
+    #[test]
+    fn problems_from_ascii_art() {
+        let problem = problem_from_ascii(
+            "
+            T.#.T
+            ..#..
+            ",
+        );
+        assert_eq!(problem.terminals, vec![(0.5, 1.5), (4.5, 1.5)]);
+        assert_eq!(problem.obstacles.len(), 2);
+        assert!(problem.coordinates_in_solid_obstacle((2.5, 1.5)));
+        assert!(problem.coordinates_in_solid_obstacle((2.5, 0.5)));
+        assert!(!problem.coordinates_in_solid_obstacle((1.5, 0.5)));
+    }
+
+    #[test]
+    fn angle_threshold_for_adding_steiner_points() {
+        let angle = 100f32.to_radians();
+        let terminals = vec![(0.0, 0.0), (1.0, 0.0), (angle.cos(), angle.sin())];
+        let problem = SteinerProblem::new(terminals, vec![]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 0, 500, 0);
+        stobga.child_buffer = vec![Individual {
+            chromosome: Chromosome {
+                steiner_points: IndexSet::new(),
+                included_corners: Corners::new(),
+                ..Default::default()
+            },
+            minimum_spanning_tree: None,
+        }];
+        stobga.build_mst(0, BufferSelector::ChildBuffer);
+        let individual = &stobga.child_buffer[0];
+        let default_threshold = StobgaConfig::default().add_steiner_angle_threshold;
+        assert_eq!(individual.add_steiner_candidates(default_threshold).len(), 1);
+        assert!(individual.add_steiner_candidates(90f32.to_radians()).is_empty());
+    }
+
+    #[test]
+    fn terminal_labels_in_geojson() {
+        let (terminals, labels) = io::parse_terminals("x,y\n0.0,0.0,north\n1.0,0.0\n0.5,1.0,south\n");
+        assert_eq!(terminals, vec![(0.0, 0.0), (1.0, 0.0), (0.5, 1.0)]);
+        let problem = SteinerProblem::new(terminals, vec![]).with_terminal_labels(labels);
+        let config = StobgaConfig {
+            max_generations: Some(1),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        stobga.run();
+        let geojson = stobga.best_solution().to_geojson(&stobga.problem);
+        let geojson: serde_json::Value = serde_json::from_str(&geojson).unwrap();
+        let terminals = geojson["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|feature| feature["properties"]["kind"] == "terminal")
+            .collect::<Vec<_>>();
+        assert_eq!(terminals.len(), 3);
+        assert_eq!(terminals[0]["properties"]["label"], "north");
+        assert_eq!(terminals[0]["geometry"]["coordinates"], serde_json::json!([0.0, 0.0]));
+        assert!(terminals[1]["properties"]["label"].is_null());
+        assert_eq!(terminals[2]["properties"]["label"], "south");
+    }
+
+    #[test]
+    fn obstacle_names_in_geojson() {
+        let obstacles = io::parse_obstacles("5\nname:river\n0.2,0.2\n0.8,0.2\n0.5,0.4\n,\nmax\n0.4,0.6\n0.6,0.6\n0.5,0.8\n");
+        assert_eq!(obstacles.len(), 2);
+        assert_eq!(obstacles[0].name.as_deref(), Some("river"));
+        assert_eq!(obstacles[0].weight, 5.0);
+        assert_eq!(obstacles[1].name, None);
+        assert_eq!(obstacles[1].weight, INF);
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (1.0, 0.0), (0.5, 1.0)], obstacles);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        let geojson = stobga.best_solution().to_geojson(&stobga.problem);
+        let geojson: serde_json::Value = serde_json::from_str(&geojson).unwrap();
+        let names = geojson["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|feature| feature["properties"]["kind"] == "obstacle")
+            .map(|feature| feature["properties"]["name"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![serde_json::json!("river"), serde_json::Value::Null]);
+    }
+
+    #[test]
+    fn entry_costs_per_crossing() {
+        let square = vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)];
+        let u_shape = vec![
+            (4.0, 0.0),
+            (7.0, 0.0),
+            (7.0, 2.0),
+            (6.0, 2.0),
+            (6.0, 1.0),
+            (5.0, 1.0),
+            (5.0, 2.0),
+            (4.0, 2.0),
+        ];
+        let obstacles = vec![
+            Obstacle {
+                entry_cost: 0.5,
+                ..Obstacle::new(2.0, square).compute_bounds()
+            },
+            Obstacle {
+                entry_cost: 0.5,
+                ..Obstacle::new(2.0, u_shape).compute_bounds()
+            },
+        ];
+        let terminals = vec![(0.0, 0.0), (8.0, 0.0), (4.0, 4.0)];
+        let problem = SteinerProblem::new(terminals, obstacles);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        // through the square once: 3 long, 1 inside, 1 entry
+        let distance = stobga.compute_distance(to_graph((0.0, 1.5)), to_graph((3.0, 1.5)));
+        assert!((distance - (3.0 + 1.0 + 0.5)).abs() < 1e-5);
+        // through both arms of the U: 5 long, 2 inside, 2 entries
+        let distance = stobga.compute_distance(to_graph((3.0, 1.5)), to_graph((8.0, 1.5)));
+        assert!((distance - (5.0 + 2.0 + 2.0 * 0.5)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn directional_obstacle_costs() {
+        // a slope of (1, 0): moving right costs 3 per length, moving left 1
+        let square = vec![(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0)];
+        let obstacles = vec![Obstacle {
+            directional: Some((1.0, 0.0)),
+            ..Obstacle::new(2.0, square).compute_bounds()
+        }];
+        let terminals = vec![(0.0, 0.5), (3.0, 0.5), (1.5, 3.0)];
+        let problem = SteinerProblem::new(terminals, obstacles);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        let forward = stobga.compute_distance(to_graph((0.0, 0.5)), to_graph((3.0, 0.5)));
+        let reverse = stobga.compute_distance(to_graph((3.0, 0.5)), to_graph((0.0, 0.5)));
+        assert!((forward - (2.0 + 3.0)).abs() < 1e-5);
+        assert!((reverse - (2.0 + 1.0)).abs() < 1e-5);
+        stobga.build_msts();
+        for individual in &stobga.population {
+            let mst = individual.minimum_spanning_tree.as_ref().unwrap();
+            assert_eq!(mst.graph.edge_count(), mst.graph.node_count() - 1);
+        }
+    }
+
+    #[test]
+    fn minimum_arborescence_breaks_cycles() {
+        // the cheapest incoming edges of 1 and 2 form a cycle
+        let edges = vec![
+            (0, 1, 5.0),
+            (0, 2, 6.0),
+            (1, 2, 1.0),
+            (2, 1, 1.0),
+            (2, 3, 2.0),
+            (0, 3, 10.0),
+        ];
+        let tree = graph::minimum_arborescence(4, 0, &edges);
+        assert_eq!(tree.len(), 3);
+        let total: f32 = tree.iter().map(|edge| edge.2).sum();
+        assert_eq!(total, 5.0 + 1.0 + 2.0);
+    }
+
+    #[test]
+    fn solving_in_3d() {
+        // an equilateral triangle in the tilted plane x + y + z = 1
+        let terminals = vec![(1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)];
+        let tree = space3d::solve(&terminals);
+        assert_eq!(tree.steiner_points.len(), 1);
+        assert_eq!(tree.edges.len(), 3);
+        let (x, y, z) = tree.steiner_points[0];
+        assert!((x + y + z - 1.0).abs() < 1e-4);
+        for coordinate in [x, y, z] {
+            assert!((coordinate - 1.0 / 3.0).abs() < 1e-3);
+        }
+        let expected = 3.0 * space3d::euclidean_distance((x, y, z), terminals[0]);
+        assert!((tree.total_weight - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn composite_termination() {
+        use crate::termination::{Composite, MaxGenerations, Stagnation};
+        let problem = problem_from_ascii(
+            "
+            T..T
+            .##.
+            T...
+            ",
+        );
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        let mut termination = Composite(vec![
+            Box::new(MaxGenerations(5)),
+            Box::new(Stagnation::new(usize::MAX)),
+        ]);
+        stobga.run_until(&mut termination);
+        assert_eq!(stobga.current_generation, 5);
+    }
+
+    #[test]
+    fn pluggable_crossover() {
+        use crate::crossover::Crossover;
+        use rand::RngCore;
+        struct CopyParents;
+        impl Crossover for CopyParents {
+            fn recombine(
+                &self,
+                p1: &Chromosome,
+                p2: &Chromosome,
+                _problem: &SteinerProblem,
+                _rng: &mut dyn RngCore,
+            ) -> (Chromosome, Chromosome) {
+                (p1.clone(), p2.clone())
+            }
+        }
+        let problem = problem_from_ascii(
+            "
+            T..T
+            .##.
+            T...
+            ",
+        );
+        let config = StobgaConfig {
+            crossover: std::sync::Arc::new(CopyParents),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        stobga.child_buffer.clear();
+        stobga.crossover(1, 60);
+        assert!(stobga.child_buffer[0].chromosome == stobga.population[1].chromosome);
+        assert!(stobga.child_buffer[1].chromosome == stobga.population[60].chromosome);
+    }
+
+    #[test]
+    fn finalize_removes_degree_two_steiner_points() {
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)];
+        let problem = SteinerProblem::new(terminals, vec![]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        stobga.build_msts();
+        // a redundant Steiner point in the middle of the bottom edge
+        stobga.population[0] = Individual {
+            chromosome: Chromosome::new(vec![(2.0, 0.0)], vec![]),
+            minimum_spanning_tree: None,
+        };
+        stobga.finalize();
+        assert_eq!(stobga.population[0].chromosome.steiner_points().count(), 0);
+        let mst = stobga.population[0].minimum_spanning_tree.as_ref().unwrap();
+        assert_eq!(mst.graph.node_count(), 3);
+        assert!((mst.total_weight - 8.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn connected_components_of_graph() {
+        let mut graph = Graph::new();
+        graph.add_edge_from_points((0.0, 0.0), (1.0, 0.0), 1.0);
+        graph.add_edge_from_points((5.0, 5.0), (6.0, 5.0), 1.0);
+        let mut components = graph.connected_components();
+        assert_eq!(components.len(), 2);
+        for component in components.iter_mut() {
+            component.sort_by(|a, b| a.0.total_cmp(&b.0));
+        }
+        components.sort_by(|a, b| a[0].0.total_cmp(&b[0].0));
+        assert_eq!(components[0], vec![(0.0, 0.0), (1.0, 0.0)]);
+        assert_eq!(components[1], vec![(5.0, 5.0), (6.0, 5.0)]);
+    }
+
+    #[test]
+    fn prim_and_kruskal_agree() {
+        let mut rng = rand_pcg::Pcg32::seed_from_u64(3);
+        let points: Vec<Point> = (0..30)
+            .map(|_| (rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect();
+        let mut graph = Graph::new();
+        // a path keeps the graph connected, the rest are random chords
+        for pair in points.windows(2) {
+            graph.add_edge_from_points(pair[0], pair[1], rng.gen_range(1.0..100.0));
+        }
+        for _ in 0..100 {
+            let a = points[rng.gen_range(0..points.len())];
+            let b = points[rng.gen_range(0..points.len())];
+            if a != b {
+                graph.add_edge_from_points(a, b, rng.gen_range(1.0..100.0));
+            }
+        }
+        let prim = graph.mst_prim();
+        let kruskal = graph.mst_kruskal();
+        assert_eq!(prim.edges.len(), points.len() - 1);
+        assert_eq!(kruskal.edges.len(), points.len() - 1);
+        let prim_weight = prim.edges.values().sum::<f32>();
+        let kruskal_weight = kruskal.edges.values().sum::<f32>();
+        assert!((prim_weight - kruskal_weight).abs() < 1e-3);
+    }
+
+    #[test]
+    fn graph_adjacency_round_trip() {
+        let mut graph = Graph::new();
+        graph.add_edge_from_points((0.0, 0.0), (0.0, 1.0), 1.0);
+        graph.add_edge_from_points((0.0, 0.0), (1.5, 1.0), 2.5);
+        graph.add_edge_from_points((1.5, 1.0), (0.0, 1.0), 4.0);
+        graph.add_node(to_graph((7.0, 7.0)));
+        let text = graph.to_adjacency_string();
+        assert_eq!(
+            text,
+            "0,0: 0,1=1; 1.5,1=2.5\n0,1: 0,0=1; 1.5,1=4\n1.5,1: 0,0=2.5; 0,1=4\n7,7:"
+        );
+        let parsed = Graph::from_adjacency_string(&text).unwrap();
+        assert_eq!(parsed.nodes, graph.nodes);
+        assert_eq!(parsed.edges, graph.edges);
+        assert!(Graph::from_adjacency_string("0,0: 1,1").is_none());
+    }
+
+    #[test]
+    fn diversity_of_populations() {
+        let problem = problem_from_ascii(
+            "
+            T..T
+            .##.
+            T...
+            ",
+        );
+        let config = StobgaConfig {
+            max_generations: Some(1),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        let varied = stobga.report().diversity;
+        assert!(varied > 0.05);
+        assert!(varied <= 1.0);
+        let clone = stobga.population[0].clone();
+        for individual in stobga.population.iter_mut() {
+            *individual = clone.clone();
+        }
+        let report = stobga.report();
+        assert!(report.diversity.abs() < 1e-6);
+        assert_eq!(report.best_weight, report.average_weight);
+        // a converged population gets immigrants
+        stobga.config.diversity_threshold = Some(0.01);
+        stobga.step();
+        assert!(stobga.diversity() > 0.0);
+    }
+
+    #[test]
+    fn bounding_circle_and_rectangle() {
+        let square = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.5, 0.5)];
+        let (center, radius) = geometry::bounding_circle(&square);
+        assert!(geometry::euclidean_distance(center, (0.5, 0.5)) < 1e-6);
+        assert!((radius - 2.0f32.sqrt() / 2.0).abs() < 1e-6);
+        assert_eq!(geometry::convex_hull(&square).len(), 4);
+        // a thin diamond: the minimum rectangle follows its slanted sides
+        let diamond = vec![(0.0, 0.0), (2.0, 2.0), (3.0, 1.0), (1.0, -1.0)];
+        let rectangle = geometry::min_area_rect(&diamond);
+        let width = geometry::euclidean_distance(rectangle[0], rectangle[1]);
+        let height = geometry::euclidean_distance(rectangle[1], rectangle[2]);
+        assert!((width * height - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn normalizing_problems() {
+        let terminals = vec![(0.0, 0.0), (100.0, 0.0), (50.0, 80.0), (100.0, 100.0)];
+        let obstacle = Obstacle::new(
+            INF,
+            vec![(40.0, 20.0), (60.0, 20.0), (60.0, 40.0), (40.0, 40.0)],
+        )
+        .compute_bounds();
+        let problem = SteinerProblem::new(terminals.clone(), vec![obstacle]);
+        let (normalized, transform) = problem.normalized();
+        assert_eq!(normalized.bounds.max_x, 1.0);
+        assert_eq!(normalized.bounds.max_y, 1.0);
+        let config = StobgaConfig {
+            max_generations: Some(5),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, normalized, config);
+        stobga.run();
+        let solution = stobga.best_solution().transformed(&transform.inverse());
+        for terminal in terminals {
+            assert!(solution.edges.iter().any(|&(a, b)| {
+                geometry::euclidean_distance(a, terminal) < 1e-3
+                    || geometry::euclidean_distance(b, terminal) < 1e-3
+            }));
+        }
+        assert!(solution.total_weight >= 100.0 + 100.0);
+    }
+
+    #[test]
+    fn wkt_import_and_export() {
+        let problem = SteinerProblem::from_wkt(
+            "MULTIPOINT ((0 0), (4 0)); POINT (2 3)",
+            "POLYGON ((1 1, 3 1, 3 2, 1 2, 1 1))",
+        )
+        .unwrap();
+        assert_eq!(problem.terminals, vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)]);
+        assert_eq!(problem.obstacles.len(), 1);
+        assert_eq!(problem.obstacles[0].weight, INF);
+        assert_eq!(problem.obstacles[0].points.len(), 4);
+        assert!(SteinerProblem::from_wkt("POINT (0 0", "").is_none());
+
+        let config = StobgaConfig {
+            max_generations: Some(2),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        stobga.run();
+        let solution = stobga.best_solution();
+        let geometries = wkt::parse_geometries(&solution.to_wkt()).unwrap();
+        assert_eq!(geometries.len(), 1);
+        assert_eq!(geometries[0].kind, "GEOMETRYCOLLECTION");
+        assert_eq!(geometries[0].members.len(), solution.edges.len());
+        assert!(geometries[0]
+            .members
+            .iter()
+            .all(|line| line.kind == "LINESTRING"));
+    }
+
+    #[test]
+    fn reading_problems_from_readers() {
+        let terminals = "x,y\n0,0,depot\n4,0\n2,3\n";
+        let obstacles = "max\n1,1\n3,1\n3,2\n\n2.5\nname:lake\n0,2\n1,2\n1,3";
+        let path = std::env::temp_dir().join("stobga_reading_problems_from_readers.txt");
+        std::fs::write(&path, terminals).unwrap();
+        let from_file = io::read_terminals(std::io::BufReader::new(
+            std::fs::File::open(&path).unwrap(),
+        ));
+        std::fs::remove_file(&path).unwrap();
+        let from_memory = io::read_terminals(terminals.as_bytes());
+        assert_eq!(from_memory, from_file);
+        assert_eq!(from_memory, io::parse_terminals(terminals));
+
+        let problem =
+            SteinerProblem::from_readers(terminals.as_bytes(), obstacles.as_bytes()).unwrap();
+        assert_eq!(problem.terminals, from_memory.0);
+        assert_eq!(problem.terminal_labels[0].as_deref(), Some("depot"));
+        let parsed = io::parse_obstacles(obstacles);
+        assert_eq!(problem.obstacles.len(), 2);
+        for (a, b) in problem.obstacles.iter().zip(parsed.iter()) {
+            assert_eq!(format!("{:?}", a), format!("{:?}", b));
+        }
+    }
+
+    #[test]
+    fn summary_after_a_short_run() {
+        let problem = problem_from_ascii(
+            "
+            T...T
+            ..#..
+            T...T
+            ",
+        );
+        let config = StobgaConfig {
+            max_generations: Some(3),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        stobga.run();
+        let summary = stobga.summary();
+        let best = stobga.best_solution();
+        assert_eq!(summary.best_weight, best.total_weight);
+        assert_eq!(summary.generations, 3);
+        assert_eq!(summary.function_evaluations, stobga.function_evaluations);
+        assert_eq!(
+            summary.steiner_count,
+            best.steiner_points.len() + best.included_corners.len()
+        );
+        assert!(summary.feasible);
+        assert!(summary.gap >= 0.0 && summary.gap < 1.0);
+        assert!(summary.to_string().starts_with("best weight"));
+    }
+
+    #[test]
+    fn removing_steiner_points_keeps_trees_feasible() {
+        // the only way around the wall leads through the Steiner point
+        let wall = Obstacle::new(INF, vec![(1.5, -5.0), (2.5, -5.0), (2.5, 5.0), (1.5, 5.0)])
+            .compute_bounds();
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0)];
+        let problem = SteinerProblem::new(terminals, vec![wall]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        stobga.child_buffer = vec![Individual {
+            chromosome: Chromosome::new(vec![(2.0, 20.0)], vec![]),
+            minimum_spanning_tree: None,
+        }];
+        stobga.mutate_remove_steiner(0);
+        let child = &stobga.child_buffer[0];
+        assert_eq!(child.chromosome.steiner_points().collect_vec(), vec![(2.0, 20.0)]);
+        assert!(child.minimum_spanning_tree.as_ref().unwrap().total_weight < INF);
+    }
+
+    #[test]
+    fn configurable_tournament_size() {
+        for tournament_size in [2, 10] {
+            let problem = problem_from_ascii(
+                "
+                T..T
+                .##.
+                T...
+                ",
+            );
+            let config = StobgaConfig {
+                tournament_size,
+                ..StobgaConfig::default()
+            };
+            let rng = rand_pcg::Pcg32::seed_from_u64(0);
+            let mut stobga = StOBGA::with_config(rng, problem, config);
+            let weight = |stobga: &StOBGA<rand_pcg::Pcg32>, i: usize| {
+                stobga.population[i]
+                    .minimum_spanning_tree
+                    .as_ref()
+                    .unwrap()
+                    .total_weight
+            };
+            for to_die in [false, true] {
+                let mut rng = stobga.random_generator.clone();
+                let candidates =
+                    rand::seq::index::sample(&mut rng, stobga.population.len(), tournament_size);
+                assert_eq!(candidates.len(), tournament_size);
+                let weights = candidates.iter().map(|i| weight(&stobga, i)).collect_vec();
+                let selected = stobga.tournament_select(to_die);
+                let expected = if to_die {
+                    weights.iter().copied().fold(f32::NEG_INFINITY, f32::max)
+                } else {
+                    weights.iter().copied().fold(f32::INFINITY, f32::min)
+                };
+                assert!(candidates.iter().any(|i| i == selected));
+                assert_eq!(weight(&stobga, selected), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn tournament_sizes_up_to_the_population_size() {
+        let problem = problem_from_ascii(
+            "
+            T..T
+            .##.
+            T...
+            ",
+        );
+        let config = |tournament_size| StobgaConfig {
+            population_size: 30,
+            t2: 3,
+            t3: 3,
+            tournament_size,
+            ..StobgaConfig::default()
+        };
+        for tournament_size in [0, 31] {
+            let rng = rand_pcg::Pcg32::seed_from_u64(0);
+            let result = StOBGA::try_with_config(rng, problem.clone(), config(tournament_size));
+            assert!(matches!(
+                result,
+                Err(SolverError::TournamentSize { size, population_size: 30 }) if size == tournament_size
+            ));
+        }
+        // every tournament is won by the best individual, yet the offspring
+        // still have distinct parents
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::try_with_config(rng, problem, config(30)).unwrap();
+        stobga.step();
+        assert_eq!(stobga.population.len(), 30);
+    }
+
+    #[test]
+    fn animated_svg() {
+        let problem = problem_from_ascii(
+            "
+            T...T
+            ..#..
+            T...T
+            ",
+        );
+        let config = StobgaConfig {
+            max_generations: Some(4),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        let svg = stobga.run_to_animated_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains("<animate"));
+        assert_eq!(svg.matches("<animate").count(), svg.matches("<g ").count());
+    }
+
+    #[test]
+    fn generation_callback() {
+        use crate::termination::MaxGenerations;
+        let problem = problem_from_ascii(
+            "
+            T..T
+            .##.
+            T...
+            ",
+        );
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        let mut generations = Vec::new();
+        stobga.run_until_with(&mut MaxGenerations(4), |stobga| {
+            generations.push(stobga.report().generation)
+        });
+        assert_eq!(generations, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn penalty_mode_for_solid_obstacles() {
+        let wall = Obstacle::new(INF, vec![(1.5, -5.0), (2.5, -5.0), (2.5, 5.0), (1.5, 5.0)])
+            .compute_bounds();
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0)];
+        let problem = SteinerProblem::new(terminals, vec![wall]);
+        let config = StobgaConfig {
+            solid_penalty: Some(1000.0),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        let distance = stobga.compute_distance(to_graph((0.0, 0.0)), to_graph((4.0, 0.0)));
+        assert!((distance - (3.0 + 1000.0)).abs() < 1e-2);
+        assert!(distance < INF);
+        stobga.population[0] = Individual {
+            chromosome: Chromosome::new(vec![], vec![]),
+            minimum_spanning_tree: None,
+        };
+        stobga.build_msts();
+        let solution = Solution::from_individual(&stobga.population[0], &stobga.problem);
+        assert!(solution.total_weight < INF);
+        assert!(!solution.feasible);
+    }
+
+    #[test]
+    fn grid_initialization() {
+        let terminals = vec![(0.0, 0.0), (3.0, 0.0), (0.0, 3.0), (3.0, 3.0)];
+        let problem = SteinerProblem::new(terminals, vec![]);
+        let config = StobgaConfig {
+            init_strategy: InitStrategy::Grid { nx: 3, ny: 3 },
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let stobga = StOBGA::with_config(rng, problem, config);
+        let grid = [0.5, 1.5, 2.5];
+        // the t2 individuals follow the single t1 individual
+        let seeded = &stobga.population[1..51];
+        assert!(seeded.iter().any(|i| i.chromosome.steiner_points().count() > 0));
+        for individual in seeded {
+            for (x, y) in individual.chromosome.steiner_points() {
+                assert!(grid.contains(&x) && grid.contains(&y), "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn kmeans_initialization() {
+        let terminals = vec![
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 1.0),
+            (1.0, 1.0),
+            (10.0, 10.0),
+            (11.0, 10.0),
+            (10.0, 11.0),
+            (11.0, 11.0),
+        ];
+        let problem = SteinerProblem::new(terminals, vec![]);
+        let config = StobgaConfig {
+            init_strategy: InitStrategy::Kmeans { k: 2 },
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let stobga = StOBGA::with_config(rng, problem, config);
+        // the first t2 individual follows the single t1 individual
+        let mut seeds = stobga.population[1].chromosome.steiner_points().collect_vec();
+        seeds.sort_by(|a, b| a.0.total_cmp(&b.0));
+        assert_eq!(seeds.len(), 2);
+        assert!(geometry::euclidean_distance(seeds[0], (0.5, 0.5)) < 1e-5);
+        assert!(geometry::euclidean_distance(seeds[1], (10.5, 10.5)) < 1e-5);
+        assert!(geometry::kmeans(&stobga.problem.terminals, 0).is_empty());
+    }
+
+    #[test]
+    fn serializing_chromosomes() {
+        let chromosome = Chromosome::new(vec![(0.25, 0.5), (1.0, -2.0)], vec![3, 0, 7]);
+        let json = serde_json::to_string(&chromosome).unwrap();
+        let parsed: Chromosome = serde_json::from_str(&json).unwrap();
+        assert!(parsed == chromosome);
+        assert_eq!(
+            parsed.steiner_points().collect_vec(),
+            vec![(0.25, 0.5), (1.0, -2.0)]
+        );
+        assert_eq!(parsed.included_corners().collect_vec(), vec![3, 0, 7]);
+
+        let individual = Individual {
+            chromosome,
+            minimum_spanning_tree: None,
+        };
+        let json = serde_json::to_string(&individual).unwrap();
+        let parsed: Individual = serde_json::from_str(&json).unwrap();
+        assert!(parsed.chromosome == individual.chromosome);
+        assert!(parsed.minimum_spanning_tree.is_none());
+    }
+
+    #[test]
+    fn displaying_solutions() {
+        let solution = Solution {
+            steiner_points: vec![(0.5, 0.3)],
+            included_corners: vec![2, 4],
+            edges: vec![((0.0, 0.0), (0.5, 0.3)), ((0.5, 0.3), (1.0, 0.0))],
+            total_weight: 1.25,
+            feasible: true,
+        };
+        assert_eq!(
+            solution.to_string(),
+            "weight 1.25, 1 Steiner points, 2 obstacle corners, feasible"
+        );
+        let individual = Individual {
+            chromosome: Chromosome::new(vec![(0.5, 0.3)], vec![2, 4]),
+            minimum_spanning_tree: None,
+        };
+        assert_eq!(
+            individual.to_string(),
+            "not evaluated, 1 Steiner points, 2 obstacle corners"
+        );
+    }
+
+    #[test]
+    fn evaluating_without_a_solver() {
+        let problem = problem_from_ascii(
+            "
+            T...T
+            ..#..
+            T...T
+            ",
+        );
+        let chromosome = Chromosome::new(vec![(1.5, 1.5), (3.5, 1.5)], vec![0, 2]);
+        let weight = problem.evaluate(&chromosome);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem.clone(), 500, 1, 50, 50);
+        stobga.population[0] = Individual {
+            chromosome: chromosome.clone(),
+            minimum_spanning_tree: None,
+        };
+        stobga.build_msts();
+        let solver_weight = stobga.population[0]
+            .minimum_spanning_tree
+            .as_ref()
+            .unwrap()
+            .total_weight;
+        assert_eq!(weight, solver_weight);
+        let mut cache = HashMap::new();
+        assert_eq!(problem.evaluate_with_cache(&chromosome, &mut cache), weight);
+        assert!(!cache.is_empty());
+        assert_eq!(problem.evaluate_with_cache(&chromosome, &mut cache), weight);
+    }
+
+    #[test]
+    fn clearance_penalizes_grazing_edges() {
+        let block = Obstacle::new(INF, vec![(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)])
+            .compute_bounds();
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0)], vec![block]);
+        let config = StobgaConfig {
+            clearance: 0.5,
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let stobga = StOBGA::with_config(rng, problem, config);
+        // passes 0.25 below the block
+        let grazing = stobga.compute_distance(to_graph((0.0, 0.75)), to_graph((4.0, 0.75)));
+        assert!((grazing - (4.0 + 0.25)).abs() < 1e-4);
+        // keeps 1.0 from the block
+        let clear = stobga.compute_distance(to_graph((0.0, 0.0)), to_graph((4.0, 0.0)));
+        assert!((clear - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn repairing_children_in_solid_obstacles() {
+        let block = Obstacle::new(INF, vec![(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)])
+            .compute_bounds();
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (0.0, 4.0), (4.0, 4.0)];
+        let problem = SteinerProblem::new(terminals, vec![block]);
+        let config = StobgaConfig {
+            repair_children: true,
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        // imported individuals with points inside the block
+        stobga.population[0].chromosome = Chromosome::new(vec![(1.5, 1.5), (3.5, 0.5)], vec![]);
+        stobga.population[1].chromosome = Chromosome::new(vec![(2.5, 2.5), (0.5, 3.5)], vec![]);
+        for _ in 0..20 {
+            stobga.crossover(0, 1);
+        }
+        for child in stobga.child_buffer.drain(..) {
+            for point in child.chromosome.steiner_points() {
+                assert!(!stobga.problem.coordinates_in_solid_obstacle(point), "{:?}", point);
+            }
+        }
+    }
+
+    #[test]
+    fn operator_statistics() {
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (0.0, 5.0), (4.0, 5.0)],
+            vec![],
+        );
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        let before = stobga.operator_stats().clone();
+        for _ in 0..3 {
+            stobga.step();
+        }
+        let stats = stobga.operator_stats();
+        let applied = |op| stats.get(op).applications - before.get(op).applications;
+        let mutations = applied(Operator::FlipMove)
+            + applied(Operator::AddSteiner)
+            + applied(Operator::RemoveSteiner);
+        // every generation recombines number_offspring / 2 pairs and mutates
+        // each of their children once
+        let number_offspring = stobga.number_offspring() as u64;
+        assert_eq!(applied(Operator::Crossover), 3 * number_offspring / 2);
+        assert_eq!(mutations, 3 * number_offspring);
+        for op in [
+            Operator::FlipMove,
+            Operator::AddSteiner,
+            Operator::RemoveSteiner,
+            Operator::Crossover,
+        ] {
+            assert!(stats.get(op).improvements <= stats.get(op).applications);
+        }
+    }
+
+    #[test]
+    fn adaptive_mutation_probabilities() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)], vec![]);
+        let config = StobgaConfig {
+            adaptive_mutation: Some(0.05),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        stobga.previous_operator_stats = stobga.operator_stats.clone();
+        for _ in 0..30 {
+            // flip-move improves half the time, adding always, removing never
+            for i in 0..10 {
+                stobga.operator_stats.record(Operator::FlipMove, i % 2 == 0);
+                stobga.operator_stats.record(Operator::AddSteiner, true);
+                stobga.operator_stats.record(Operator::RemoveSteiner, false);
+            }
+            stobga.adapt_mutation_probabilities(0.05);
+        }
+        let [flip_move, add_steiner, remove_steiner] = stobga.mutation_probabilities;
+        assert!((flip_move + add_steiner + remove_steiner - 1.0).abs() < 1e-4);
+        assert!((remove_steiner - 0.05).abs() < 1e-3);
+        assert!(add_steiner > 0.6);
+        assert!(add_steiner > flip_move && flip_move > remove_steiner);
+        stobga.step();
+    }
+
+    #[test]
+    fn pareto_front_is_non_dominated() {
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (0.0, 5.0), (4.0, 5.0)],
+            vec![],
+        );
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        for _ in 0..5 {
+            stobga.step();
+        }
+        let front = stobga.pareto_front();
+        assert!(!front.is_empty());
+        let count = |s: &Solution| s.steiner_points.len() + s.included_corners.len();
+        for a in &front {
+            for b in &front {
+                if !std::ptr::eq(a, b) {
+                    let dominated = a.total_weight <= b.total_weight
+                        && count(a) <= count(b)
+                        && (a.total_weight < b.total_weight || count(a) < count(b));
+                    assert!(!dominated, "{} dominates {}", a, b);
+                }
+            }
+        }
+        assert!(front.windows(2).all(|w| count(&w[0]) < count(&w[1])));
+    }
+
+    #[test]
+    fn rejecting_non_finite_coordinates() {
+        let (terminals, _) = io::parse_terminals("x,y\n0,0\ninf,1\n2,2\n");
+        assert_eq!(
+            SteinerProblem::try_new(terminals, vec![]).err(),
+            Some(SolverError::NonFiniteCoordinate {
+                x: f32::INFINITY,
+                y: 1.0
+            })
+        );
+        let obstacles = "1.5\n0,0\nNaN,1\n1,1\n";
+        let terminals = "x,y\n0,0\n2,2\n";
+        let result = SteinerProblem::from_readers(terminals.as_bytes(), obstacles.as_bytes());
+        assert!(matches!(result, Err(SolverError::NonFiniteCoordinate { .. })));
+    }
+
+    #[test]
+    fn angles_of_nearly_collinear_points() {
+        let (vertex, a, b) = ((0.0, 0.0), (0.7, 0.3), (-1.19, -0.51000005));
+        // rounding pushes the cosine below -1
+        let dot: f32 = a.0 * b.0 + a.1 * b.1;
+        let den = (a.0 * a.0 + a.1 * a.1).sqrt() * (b.0 * b.0 + b.1 * b.1).sqrt();
+        assert!(dot / den < -1.0);
+        assert!((dot / den).acos().is_nan());
+        let angle = geometry::angle(vertex, a, b);
+        assert!((angle - std::f32::consts::PI).abs() < 1e-3, "{}", angle);
+    }
+
+    #[test]
+    fn flip_move_avoids_terminals() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (0.5, 0.0), (0.0, 0.5)], vec![]);
+        let mut rng = rand_pcg::Pcg32::seed_from_u64(0);
+        // late generations move by exactly M_RANGE_MIN in both directions,
+        // so a quarter of the moves would land on the terminal at the origin
+        for _ in 0..100 {
+            let mut individual = Individual {
+                chromosome: Chromosome::new(vec![(M_RANGE_MIN, M_RANGE_MIN)], vec![]),
+                minimum_spanning_tree: None,
+            };
+            individual.mutation_flip_move(&problem, &mut rng, 2000, problem.obstacle_corners.len(), SteinerRegion::default());
+            let points: Vec<Point> = individual.chromosome.steiner_points().collect();
+            assert_eq!(points.len(), 1);
+            assert!(euclidean_distance(points[0], (0.0, 0.0)) >= EPSILON);
+        }
+    }
+
+    #[test]
+    fn delaunay_centroids() {
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (0.0, 5.0), (4.0, 5.0)];
+        let problem = SteinerProblem::new(terminals.clone(), vec![]);
+        let centroids = problem.centroids().to_vec();
+        assert!(!centroids.is_empty());
+        let hull = geometry::convex_hull(&terminals);
+        for &centroid in &centroids {
+            for i in 0..hull.len() {
+                let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+                let cross = (b.0 - a.0) * (centroid.1 - a.1) - (b.1 - a.1) * (centroid.0 - a.0);
+                assert!(cross >= -1e-4, "{:?} outside of the hull", centroid);
+            }
+        }
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        let svg = stobga.instance_to_svg_with_centroids(0);
+        assert_eq!(svg.matches("#9B59B6").count(), centroids.len());
+    }
+
+    #[test]
+    fn precomputed_terminal_distances() {
+        let wall = Obstacle::new(INF, vec![(1.5, -5.0), (2.5, -5.0), (2.5, 5.0), (1.5, 5.0)])
+            .compute_bounds();
+        let swamp = Obstacle::new(3.0, vec![(0.5, 1.0), (3.5, 1.0), (3.5, 2.0), (0.5, 2.0)])
+            .compute_bounds();
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (0.0, 3.0), (4.0, 3.0)];
+        let problem = SteinerProblem::new(terminals.clone(), vec![wall, swamp]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        let n = terminals.len();
+        for (i, &from) in terminals.iter().enumerate() {
+            for (j, &to) in terminals.iter().enumerate() {
+                let expected = stobga.compute_distance(to_graph(from), to_graph(to));
+                assert_eq!(stobga.terminal_distances[i * n + j], expected);
+            }
+        }
+        let individual = &stobga.population[0];
+        let on_the_fly = stobga.problem.evaluate(&individual.chromosome);
+        assert!((individual.weight() - on_the_fly).abs() < 1e-3);
+    }
+
+    #[test]
+    fn steady_state_replacement() {
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (0.0, 5.0), (4.0, 5.0)],
+            vec![],
+        );
+        let config = StobgaConfig {
+            replacement: ReplacementStrategy::SteadyState,
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        stobga.sort_population();
+        let mut best_weight = stobga.population[0].weight();
+        let evaluations = stobga.function_evaluations;
+        for _ in 0..50 {
+            stobga.step();
+            assert_eq!(stobga.population.len(), POPULATION_SIZE);
+            let weight = stobga.population[0].weight();
+            assert!(weight <= best_weight);
+            best_weight = weight;
+        }
+        // two children per step, each evaluated at most before and after its
+        // mutation
+        assert!(stobga.function_evaluations - evaluations <= 50 * 4);
+    }
+
+    #[test]
+    fn single_child_crossover() {
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (0.0, 5.0), (4.0, 5.0)],
+            vec![],
+        );
+        let config = StobgaConfig {
+            single_child: true,
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        for _ in 0..5 {
+            stobga.crossover(0, 1);
+        }
+        assert_eq!(stobga.child_buffer.len(), 5);
+        stobga.child_buffer.clear();
+        for _ in 0..3 {
+            stobga.step();
+            assert_eq!(stobga.population.len(), POPULATION_SIZE);
+        }
+    }
+
+    #[test]
+    fn branch_points_and_leaves() {
+        let center = (2.0, 2.0);
+        let leaves = [(0.0, 2.0), (4.0, 2.0), (2.0, 0.0), (2.0, 4.0), (4.0, 4.0)];
+        let mut edges: Vec<(Point, Point)> = leaves.iter().map(|&leaf| (center, leaf)).collect();
+        // a path through (3.0, 3.0) towards the last leaf
+        edges.pop();
+        edges.push((center, (3.0, 3.0)));
+        edges.push(((3.0, 3.0), (4.0, 4.0)));
+        let star = Solution {
+            steiner_points: vec![center, (3.0, 3.0)],
+            included_corners: vec![],
+            edges,
+            total_weight: 0.0,
+            feasible: true,
+        };
+        assert_eq!(star.branch_point_count(), 1);
+        assert_eq!(star.leaf_count(), 5);
+    }
+
+    #[test]
+    fn snapping_solutions_to_a_grid() {
+        let block = Obstacle::new(INF, vec![(1.86, 1.86), (2.14, 1.86), (2.14, 2.14), (1.86, 2.14)])
+            .compute_bounds();
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.5)];
+        let problem = SteinerProblem::new(terminals, vec![block]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        stobga.population[0] = Individual {
+            // the second point lies next to the block, but its nearest grid
+            // point (2.1, 2.0) is inside
+            chromosome: Chromosome::new(vec![(2.0132, 1.1547), (2.141, 2.0)], vec![]),
+            minimum_spanning_tree: None,
+        };
+        stobga.build_msts();
+        let mut solution = Solution::from_individual(&stobga.population[0], &stobga.problem);
+        assert!(solution.feasible);
+        solution.snap_to_grid(0.1, &stobga.problem);
+        for &(x, y) in &solution.steiner_points {
+            for coordinate in [x, y] {
+                let multiple = (coordinate / 0.1).round() * 0.1;
+                assert!((coordinate - multiple).abs() < 1e-5, "{}", coordinate);
+            }
+            assert!(!stobga.problem.coordinates_in_solid_obstacle((x, y)));
+        }
+        assert!(solution
+            .steiner_points
+            .iter()
+            .any(|&p| euclidean_distance(p, (2.0, 1.2)) < 1e-5));
+        assert!(solution
+            .steiner_points
+            .iter()
+            .any(|&p| euclidean_distance(p, (2.2, 2.0)) < 1e-5));
+        assert!(solution.feasible);
+        assert!(solution.total_weight < INF);
+    }
+
+    #[test]
+    #[should_panic]
+    fn snapping_to_a_non_positive_grid() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (1.0, 0.0)], vec![]);
+        let chromosome = Chromosome::new(vec![(0.5, 0.1)], vec![]);
+        let mst = problem.spanning_tree(&chromosome, &mut HashMap::new(), &StobgaConfig::default(), None);
+        let individual = Individual {
+            chromosome,
+            minimum_spanning_tree: Some(mst),
+        };
+        let mut solution = Solution::from_individual(&individual, &problem);
+        solution.snap_to_grid(0.0, &problem);
+    }
+
+    #[test]
+    fn parallel_initialization() {
+        let block = Obstacle::new(INF, vec![(1.0, 1.0), (3.0, 1.0), (3.0, 2.0), (1.0, 2.0)])
+            .compute_bounds();
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (0.0, 5.0), (4.0, 5.0)];
+        let problem = SteinerProblem::new(terminals, vec![block]);
+        let build = |parallel_init| {
+            let config = StobgaConfig {
+                parallel_init,
+                ..StobgaConfig::default()
+            };
+            let rng = rand_pcg::Pcg32::seed_from_u64(7);
+            StOBGA::with_config(rng, problem.clone(), config)
+        };
+        let (sequential, parallel) = (build(false), build(true));
+        for (s, p) in sequential.population.iter().zip(&parallel.population) {
+            assert!(s.chromosome == p.chromosome);
+            assert_eq!(s.weight(), p.weight());
+        }
+        let best = |stobga: &StOBGA<_>| {
+            stobga
+                .population
+                .iter()
+                .map(|individual| individual.weight())
+                .fold(INF, f32::min)
+        };
+        assert_eq!(best(&sequential), best(&parallel));
+        assert_eq!(sequential.function_evaluations, parallel.function_evaluations);
+    }
+
+    #[test]
+    fn reporting_obstacle_crossings() {
+        let swamp = Obstacle::new(2.0, vec![(1.0, -1.0), (3.0, -1.0), (3.0, 1.0), (1.0, 1.0)])
+            .compute_bounds();
+        let far_away = Obstacle::new(5.0, vec![(0.0, 4.0), (1.0, 4.0), (1.0, 5.0)]).compute_bounds();
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0)], vec![far_away, swamp]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        stobga.population[0] = Individual {
+            chromosome: Chromosome::new(vec![], vec![]),
+            minimum_spanning_tree: None,
+        };
+        stobga.build_msts();
+        let solution = Solution::from_individual(&stobga.population[0], &stobga.problem);
+        let crossings = solution.obstacle_crossings(&stobga.problem);
+        assert_eq!(crossings.len(), 1);
+        let (index, cost) = crossings[0];
+        assert_eq!(index, 1);
+        // two units through the swamp, at twice the cost
+        assert!((cost - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn sampling_bounds_override() {
+        let terminals = vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)];
+        let wide = Bounds {
+            min_x: -10.0,
+            max_x: 10.0,
+            min_y: -10.0,
+            max_y: 10.0,
+        };
+        let problem = SteinerProblem::new(terminals, vec![]).with_sampling_bounds(wide);
+        assert_eq!(problem.bounds().max_x, 1.0);
+        assert_eq!(problem.sampling_bounds().max_x, 10.0);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        let outside = stobga
+            .population
+            .iter()
+            .flat_map(|individual| individual.chromosome.steiner_points())
+            .filter(|&(x, y)| !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y))
+            .count();
+        assert!(outside > 0);
+    }
+
+    #[test]
+    fn merging_duplicate_terminals() {
+        let problem = SteinerProblem::new(vec![(1.0, 1.0), (1.0, 1.0)], vec![]);
+        assert_eq!(problem.terminals.len(), 1);
+        assert_eq!(problem.average_terminal_distance, 0.0);
+        let labels = vec![Some("a".to_string()), None, Some("c".to_string())];
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (0.0, 1e-7), (3.0, 4.0)], vec![])
+            .with_terminal_labels(labels);
+        assert_eq!(problem.terminals, vec![(0.0, 0.0), (3.0, 4.0)]);
+        assert_eq!(problem.terminal_labels[1].as_deref(), Some("c"));
+        // one pair at distance 5, counted in both directions
+        assert_eq!(problem.average_terminal_distance, 5.0);
+    }
+
+    #[test]
+    fn bounding_box_precheck() {
+        let square = |min: f32, max: f32| Bounds {
+            min_x: min,
+            max_x: max,
+            min_y: min,
+            max_y: max,
+        };
+        assert!(overlap(&square(0.0, 1.0), &square(1.0, 2.0)));
+        assert!(overlap(&square(0.0, 3.0), &square(1.0, 2.0)));
+        assert!(!overlap(&square(0.0, 1.0), &square(1.5, 2.0)));
+        // a diagonal entering a swamp exactly at its corner
+        let swamp = Obstacle::new(3.0, vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)])
+            .compute_bounds();
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (2.0, 2.0)], vec![swamp]);
+        let config = StobgaConfig::default();
+        let cost = problem.edge_cost(to_graph((0.0, 0.0)), to_graph((1.0, 1.0)), &config);
+        assert!((cost - 2f32.sqrt()).abs() < 1e-5);
+        let cost = problem.edge_cost(to_graph((0.0, 0.0)), to_graph((2.0, 2.0)), &config);
+        assert!((cost - 4.0 * 2f32.sqrt()).abs() < 1e-4, "{}", cost);
+    }
+
+    #[test]
+    fn keeping_steiner_points_in_the_terminal_hull() {
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (1.0, 1.0)];
+        let problem = SteinerProblem::new(terminals.clone(), vec![]);
+        let config = StobgaConfig {
+            hull_constraint: true,
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        for _ in 0..5 {
+            stobga.step();
+        }
+        stobga.finalize();
+        let hull = geometry::convex_hull(&terminals);
+        let inside = |p: Point| {
+            (0..hull.len()).all(|i| {
+                let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+                (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0) >= -1e-4
+            })
+        };
+        for individual in &stobga.population {
+            for point in individual.chromosome.steiner_points() {
+                assert!(inside(point), "{:?}", point);
+            }
+        }
+        let graph = &stobga.population[0].minimum_spanning_tree.as_ref().unwrap().graph;
+        assert!(graph.node_weights().all(|&p| inside(p)));
+    }
+
+    #[test]
+    fn iterated_fermat_relocation() {
+        let terminals = vec![(0.0, 0.0), (0.0, 2.0), (4.0, 0.0), (4.0, 2.0)];
+        let problem = SteinerProblem::new(terminals, vec![]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        stobga.population[0] = Individual {
+            chromosome: Chromosome::new(vec![(1.5, 1.2), (2.5, 0.8)], vec![]),
+            minimum_spanning_tree: None,
+        };
+        stobga.build_msts();
+        let start = stobga.population[0].clone();
+        stobga.relocate_to_fermat_points(0, 1);
+        let single_pass = stobga.population[0].weight();
+        stobga.population[0] = start.clone();
+        stobga.relocate_to_fermat_points(0, FINALIZE_ITERATIONS);
+        let iterated = stobga.population[0].weight();
+        assert!(single_pass < start.weight());
+        assert!(iterated < single_pass - 1e-3, "{} vs {}", iterated, single_pass);
+        // the optimal tree: two Steiner points on the middle line
+        let optimum = 4.0 + 6.0 / 3f32.sqrt();
+        assert!(iterated >= optimum - 1e-3);
+        let chromosome = &stobga.population[0].chromosome;
+        assert_eq!(chromosome.steiner_points().count(), 2);
+        let graph = &stobga.population[0].minimum_spanning_tree.as_ref().unwrap().graph;
+        for point in chromosome.steiner_points() {
+            assert!(graph.node_weights().any(|&p| p == point));
+        }
+    }
+
+    #[test]
+    fn iterating_the_population() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)], vec![]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        stobga.step();
+        let mut count = 0;
+        for (i, (chromosome, weight)) in stobga.population_iter().enumerate() {
+            let individual = &stobga.population[i];
+            assert!(*chromosome == individual.chromosome);
+            assert_eq!(weight, individual.minimum_spanning_tree.as_ref().unwrap().total_weight);
+            count += 1;
+        }
+        assert_eq!(count, POPULATION_SIZE);
+    }
+
+    #[test]
+    fn steiner_points_stay_in_the_region_polygon() {
+        let terminals = vec![(0.0, 0.0), (10.0, 0.0), (0.0, 10.0), (10.0, 10.0), (4.0, 4.0)];
+        let problem = SteinerProblem::new(terminals, vec![]);
+        // an L shape leaving out the upper right quarter
+        let region = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 5.0),
+            (5.0, 5.0),
+            (5.0, 10.0),
+            (0.0, 10.0),
+        ];
+        let config = StobgaConfig {
+            steiner_region_polygon: Some(region),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        for _ in 0..5 {
+            stobga.step();
+        }
+        stobga.finalize();
+        for individual in &stobga.population {
+            for (x, y) in individual.chromosome.steiner_points() {
+                assert!(!(x >= 5.0 && y >= 5.0), "{:?}", (x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn bounding_box_fill_ratio() {
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (0.0, 4.0), (4.0, 4.0)];
+        let problem = SteinerProblem::new(terminals, vec![]);
+        let solution = Solution {
+            steiner_points: vec![(1.0, 1.0)],
+            included_corners: vec![],
+            edges: vec![((0.0, 0.0), (1.0, 1.0)), ((1.0, 1.0), (2.0, 2.0))],
+            total_weight: 2.0 * 2f32.sqrt(),
+            feasible: true,
+        };
+        assert!((solution.bbox_fill_ratio(&problem) - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn overlapping_obstacles_cost_the_maximum_weight() {
+        let square = |x: f32, weight: f32| {
+            Obstacle::new(weight, vec![(x, 0.0), (x + 2.0, 0.0), (x + 2.0, 2.0), (x, 2.0)])
+                .compute_bounds()
+        };
+        let terminals = vec![(-1.0, 1.0), (4.0, 1.0)];
+        let config = StobgaConfig::default();
+        let problem = SteinerProblem::new(terminals.clone(), vec![square(0.0, 2.0), square(1.0, 3.0)]);
+        // 1 outside, 1 in the first only, 1 in both, 1 in the second only,
+        // 1 outside
+        let cost = problem.edge_cost(to_graph((-1.0, 1.0)), to_graph((4.0, 1.0)), &config);
+        assert!((cost - 10.0).abs() < 1e-4, "{}", cost);
+
+        let problem = SteinerProblem::new(terminals, vec![square(0.0, 2.0), square(1.0, INF)]);
+        let cost = problem.edge_cost(to_graph((-1.0, 1.0)), to_graph((4.0, 1.0)), &config);
+        assert_eq!(cost, INF);
+    }
+
+    #[test]
+    fn spanning_trees_do_not_depend_on_insertion_order() {
+        let block = Obstacle::new(2.0, vec![(3.0, -0.5), (3.5, -0.5), (3.5, 0.5), (3.0, 0.5)])
+            .compute_bounds();
+        let problem =
+            SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (2.0, -3.0)], vec![block]);
+        let points = [(2.0, 1.0), (2.0, -1.0), (1.0, 0.0)];
+        let chromosome = |order: [usize; 3]| Chromosome {
+            steiner_points: order.iter().map(|&i| to_graph(points[i])).collect(),
+            included_corners: order.iter().copied().collect(),
+            ..Default::default()
+        };
+        let config = StobgaConfig::default();
+        let t1 = problem.spanning_tree(&chromosome([0, 1, 2]), &mut HashMap::new(), &config, None);
+        let t2 = problem.spanning_tree(&chromosome([2, 0, 1]), &mut HashMap::new(), &config, None);
+        assert_eq!(
+            t1.graph.node_weights().collect::<Vec<_>>(),
+            t2.graph.node_weights().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            t1.graph.edge_references().map(|e| (e.source(), e.target())).collect::<Vec<_>>(),
+            t2.graph.edge_references().map(|e| (e.source(), e.target())).collect::<Vec<_>>()
+        );
+        assert_eq!(t1.total_weight, t2.total_weight);
+    }
+
+    #[test]
+    fn solving_in_one_call() {
+        let config = StobgaConfig {
+            max_generations: Some(20),
+            ..StobgaConfig::default()
+        };
+        let solution = solve(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)], vec![], config);
+        assert!(solution.feasible);
+        assert!(solution.total_weight > 0.0);
+    }
+
+    #[test]
+    fn tracking_when_the_best_was_found() {
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (0.0, 5.0), (4.0, 5.0)],
+            vec![],
+        );
+        let config = StobgaConfig {
+            population_size: 50,
+            t2: 5,
+            t3: 5,
+            recession_duration: 10,
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem.clone(), config.clone());
+        assert_eq!(stobga.best_found_generation(), 0);
+        let mut best = (INF, 0);
+        for _ in 0..30 {
+            stobga.step();
+            let weight = stobga.population[0].weight();
+            if util::is_improvement_by_factor(best.0, weight, IMPROVEMENT_FACTOR) {
+                best = (weight, stobga.current_generation);
+            }
+        }
+        assert_eq!(stobga.best_found_generation(), best.1);
+        assert_eq!(stobga.summary().best_found_generation, stobga.best_found_generation());
+
+        // the run stops on the same improvements the generation is tracked by
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        stobga.run();
+        assert!(stobga.best_found_generation() > 0);
+        assert!(stobga.best_found_generation() <= stobga.current_generation - 10);
+    }
+
+    #[test]
+    fn quadtree_nearest_matches_a_linear_scan() {
+        let mut rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut points: Vec<Point> = (0..500)
+            .map(|_| (rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect();
+        // a clump of coincident points must not break the splitting
+        points.extend(std::iter::repeat_n((5.0, 5.0), 20));
+        let tree = crate::quadtree::QuadTree::new(points.iter().copied());
+        assert!(crate::quadtree::QuadTree::new(vec![]).nearest((0.0, 0.0)).is_none());
+        for _ in 0..200 {
+            let query = (rng.gen_range(-2.0..12.0), rng.gen_range(-2.0..12.0));
+            let linear = points
+                .iter()
+                .map(|&p| euclidean_distance(p, query))
+                .fold(f32::INFINITY, f32::min);
+            let (_, distance) = tree.nearest(query).unwrap();
+            assert_eq!(distance, linear);
+        }
+    }
+
+    #[test]
+    fn chromosome_quadtree_follows_its_steiner_points() {
+        let mut chromosome = Chromosome::new(vec![(0.0, 0.0), (4.0, 0.0)], vec![]);
+        assert_eq!(chromosome.nearest_steiner_point((1.0, 0.0)), Some(((0.0, 0.0), 1.0)));
+        chromosome.steiner_points_mut().insert(to_graph((2.0, 0.0)));
+        assert_eq!(chromosome.nearest_steiner_point((1.5, 0.0)), Some(((2.0, 0.0), 0.5)));
+        chromosome.steiner_points_mut().shift_remove(&to_graph((2.0, 0.0)));
+        assert_eq!(chromosome.nearest_steiner_point((1.5, 0.0)), Some(((0.0, 0.0), 1.5)));
+        // the cached tree does not tell chromosomes apart
+        let clone = chromosome.clone();
+        assert!(clone == chromosome);
+        assert!(Chromosome::default().nearest_steiner_point((0.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn limiting_the_edge_length() {
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)];
+        let problem = SteinerProblem::new(terminals.clone(), vec![]);
+        let (a, b) = (to_graph((0.0, 0.0)), to_graph((4.0, 0.0)));
+        let penalized = StobgaConfig {
+            max_edge_length: Some(3.0),
+            max_edge_length_penalty: Some(10.0),
+            ..StobgaConfig::default()
+        };
+        assert!((problem.edge_cost(a, b, &penalized) - 14.0).abs() < 1e-5);
+
+        let config = StobgaConfig {
+            max_edge_length: Some(3.0),
+            ..StobgaConfig::default()
+        };
+        assert_eq!(problem.edge_cost(a, b, &config), INF);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        for _ in 0..10 {
+            stobga.step();
+        }
+        stobga.finalize();
+        let solution = stobga.best_solution();
+        assert!(solution.total_weight < INF);
+        assert!(solution.max_edge_length() <= 3.0);
+    }
+
+    #[test]
+    fn obstacle_containment_and_intersection() {
+        let obstacle = wrapped_obstacle();
+        let (points, bounds) = (&obstacle.points, &obstacle.bounds);
+        let samples = [(0.25, 0.65), (0.17, 0.62), (0.3, 0.7), (0.1, 0.1), (0.39, 0.76), (0.2, 0.59)];
+        for p in samples {
+            assert_eq!(obstacle.contains(p), point_in_polygon(p.0, p.1, points, bounds));
+        }
+        assert!(obstacle.contains((0.25, 0.65)));
+        for (i, &a) in points.iter().enumerate() {
+            for &b in points[i + 1..].iter().chain(samples.iter()) {
+                assert_eq!(
+                    obstacle.intersects_segment(a, b),
+                    intersection_length(a.0, a.1, b.0, b.1, points, bounds) > 0.0
+                );
+            }
+        }
+        assert!(!obstacle.intersects_segment(points[0], points[1]));
+        assert!(obstacle.intersects_segment((0.1, 0.65), (0.4, 0.65)));
+    }
+
+    #[test]
+    fn runs_are_reproducible_with_other_generators() {
+        fn run<R: Rng + SeedableRng>(seed: u64) -> (Vec<Chromosome>, f32) {
+            let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (3.0, 4.0)], vec![]);
+            let mut stobga = StOBGA::<R>::from_seed(seed, problem, StobgaConfig::default());
+            for _ in 0..3 {
+                stobga.step();
+            }
+            let chromosomes = stobga.population_iter().map(|(c, _)| c.clone()).collect();
+            (chromosomes, stobga.best_solution().total_weight)
+        }
+        assert!(run::<rand_pcg::Pcg32>(7) == run::<rand_pcg::Pcg32>(7));
+        assert!(run::<rand::rngs::StdRng>(7) == run::<rand::rngs::StdRng>(7));
+        assert!(run::<rand_pcg::Pcg32>(7) != run::<rand::rngs::StdRng>(7));
+    }
+
+    #[test]
+    fn exporting_the_history_as_csv() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (3.0, 4.0)], vec![]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        let history = stobga.run_until_with_history(&mut MaxGenerations(5));
+        assert_eq!(history.len(), 5);
+        let csv = history_to_csv(&history);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "generation,best_weight,average_weight,function_evaluations,seconds");
+        assert_eq!(lines.len(), history.len() + 1);
+        let best: Vec<f32> = lines[1..]
+            .iter()
+            .map(|line| line.split(',').nth(1).unwrap().parse().unwrap())
+            .collect();
+        assert!(best.windows(2).all(|pair| pair[1] <= pair[0]));
+    }
+
+    #[test]
+    fn disabling_obstacle_corners() {
+        let block = Obstacle::new(INF, vec![(1.0, 1.0), (3.0, 1.0), (3.0, 2.0), (1.0, 2.0)])
+            .compute_bounds();
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (0.0, 5.0), (4.0, 5.0)];
+        let problem = SteinerProblem::new(terminals, vec![block]);
+        let config = StobgaConfig {
+            use_obstacle_corners: false,
+            diversity_threshold: Some(1.0),
+            ..StobgaConfig::default()
+        };
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        let no_corners = |stobga: &StOBGA<_>| {
+            stobga
+                .population
+                .iter()
+                .all(|individual| individual.chromosome.included_corners().next().is_none())
+        };
+        assert!(no_corners(&stobga));
+        for _ in 0..5 {
+            stobga.step();
+            assert!(no_corners(&stobga));
+        }
+    }
+
+    #[test]
+    fn precomputed_obstacle_edges() {
+        let obstacle = problematic_obstacle();
+        assert_eq!(obstacle.edges.len(), obstacle.points.len());
+        let mut segments = vec![((0.182, 0.126), (0.31, 0.018)), ((0.0, 0.1), (0.6, 0.1))];
+        for (i, &a) in obstacle.points.iter().enumerate() {
+            for &b in &obstacle.points[i + 1..] {
+                segments.push((a, b));
+            }
+        }
+        for (a, b) in segments {
+            assert_eq!(
+                geometry::edges_intersection_length_and_entries(a.0, a.1, b.0, b.1, obstacle.edges.iter().copied()),
+                geometry::intersection_length_and_entries(a.0, a.1, b.0, b.1, &obstacle.points, &obstacle.bounds)
+            );
+        }
+    }
+
+    #[test]
+    fn relative_improvements() {
+        use util::{is_first_or_improvement_by_factor, is_improvement_by_factor, is_increase_by_factor};
+        // 0.005% is below the 0.01% factor
+        assert!(!is_improvement_by_factor(100.0, 99.995, IMPROVEMENT_FACTOR));
+        assert!(is_improvement_by_factor(100.0, 99.98, IMPROVEMENT_FACTOR));
+        assert!(is_improvement_by_factor(INF, 1000.0, IMPROVEMENT_FACTOR));
+        assert!(!is_improvement_by_factor(INF, 2.0 * INF, IMPROVEMENT_FACTOR));
+        assert!(is_first_or_improvement_by_factor(None, 2.0 * INF, IMPROVEMENT_FACTOR));
+        assert!(!is_first_or_improvement_by_factor(Some(100.0), 99.995, IMPROVEMENT_FACTOR));
+        assert!(!is_increase_by_factor(100.0, 100.005, IMPROVEMENT_FACTOR));
+        assert!(is_increase_by_factor(100.0, 100.02, IMPROVEMENT_FACTOR));
+    }
+
+    #[test]
+    fn collinear_terminals() {
+        let terminals = vec![(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0)];
+        let problem = SteinerProblem::new(terminals, vec![]);
+        assert!(problem.centroids().is_empty());
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        stobga.step();
+        stobga.finalize();
+        assert!((stobga.best_solution().total_weight - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn centroids_of_the_delaunay_triangles() {
+        // the only Delaunay triangulation splits along the x axis, the
+        // circumcircle of either triangle leaves out the opposite apex
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (2.0, -3.0)];
+        let problem = SteinerProblem::new(terminals, vec![]);
+        let mut centroids = problem.centroids().to_vec();
+        centroids.sort_by(|a, b| a.1.total_cmp(&b.1));
+        assert_eq!(centroids.len(), 2);
+        for (centroid, expected) in centroids.iter().zip([(2.0, -1.0), (2.0, 1.0)]) {
+            assert!(euclidean_distance(*centroid, expected) < 1e-5, "{:?}", centroid);
+        }
+    }
+
+    #[test]
+    fn solving_reports_the_cost_of_the_run() {
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)];
+        let config = || StobgaConfig {
+            max_generations: Some(5),
+            ..StobgaConfig::default()
+        };
+        let (solution, summary) = solve_with_summary(terminals.clone(), vec![], config());
+        let mut stobga = StOBGA::<rand_pcg::Pcg32>::from_seed(0, SteinerProblem::new(terminals, vec![]), config());
+        stobga.run();
+        assert_eq!(summary.function_evaluations, stobga.function_evaluations);
+        assert_eq!(summary.best_weight, solution.total_weight);
+        assert!(summary.elapsed.is_some());
+    }
+
+    #[test]
+    fn fast_corridors_and_invalid_weights() {
+        let corridor = |weight: f32| {
+            Obstacle::new(weight, vec![(1.0, -1.0), (3.0, -1.0), (3.0, 1.0), (1.0, 1.0)]).compute_bounds()
+        };
+        let terminals = vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)];
+        let problem = SteinerProblem::new(terminals.clone(), vec![corridor(0.5)]);
+        let (a, b) = ((0.0, 0.0), (4.0, 0.0));
+        let cost = problem.edge_cost(to_graph(a), to_graph(b), &StobgaConfig::default());
+        assert!(cost < euclidean_distance(a, b));
+        assert!((cost - 3.0).abs() < 1e-5);
+
+        for weight in [0.0, -1.0, f32::NAN] {
+            assert!(matches!(
+                SteinerProblem::try_new(terminals.clone(), vec![corridor(weight)]),
+                Err(SolverError::NonPositiveWeight { obstacle: 0, .. })
+            ));
+        }
+        let negative_entry = Obstacle {
+            entry_cost: -1.0,
+            ..corridor(2.0)
+        };
+        assert!(matches!(
+            SteinerProblem::try_new(terminals, vec![negative_entry]),
+            Err(SolverError::NegativeEntryCost { obstacle: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn adding_random_steiner_points_gives_up() {
+        let block = Obstacle::new(INF, vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)])
+            .compute_bounds();
+        let terminals = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+        let problem = SteinerProblem::new(terminals, vec![block]);
+        let mut individual = Individual {
+            chromosome: Chromosome::new(vec![], vec![]),
+            minimum_spanning_tree: None,
+        };
+        individual.minimum_spanning_tree = Some(problem.spanning_tree(
+            &individual.chromosome,
+            &mut HashMap::new(),
+            &StobgaConfig::default(),
+            None,
+        ));
+        let mut rng = rand_pcg::Pcg32::seed_from_u64(0);
+        // no angle is below 0, so a random point is tried, which always
+        // lies in the block
+        individual.mutation_add_steiner(&problem, &mut rng, 0.0, SteinerRegion::default());
+        assert_eq!(individual.chromosome.steiner_points().count(), 0);
+    }
+
+    #[test]
+    fn tournament_ties_go_to_the_lowest_index() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)], vec![]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 500, 1, 50, 50);
+        let heavy = Individual {
+            chromosome: Chromosome::new(vec![(9.0, 9.0)], vec![]),
+            minimum_spanning_tree: None,
+        };
+        let light = Individual {
+            chromosome: Chromosome::new(vec![(2.0, 1.0)], vec![]),
+            minimum_spanning_tree: None,
+        };
+        for index in [3, 7, 9] {
+            stobga.population[index] = heavy.clone();
+        }
+        for index in [4, 8] {
+            stobga.population[index] = light.clone();
+        }
+        stobga.build_msts();
+        assert_eq!(stobga.tournament_winner([9, 7, 3].into_iter(), false), 3);
+        assert_eq!(stobga.tournament_winner([9, 7, 3].into_iter(), true), 3);
+        assert_eq!(stobga.tournament_winner([9, 8, 4, 3].into_iter(), false), 4);
+        assert_eq!(stobga.tournament_winner([8, 9, 4, 7].into_iter(), true), 7);
+    }
+
+    #[test]
+    fn terminal_mst_weight_is_cached() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (0.0, 3.0)], vec![]);
+        let mut stobga = small_stobga(problem);
+        assert!(stobga.problem.terminal_mst_weight.get().is_none());
+        assert!((stobga.terminal_mst_weight() - 7.0).abs() < EPSILON);
+        // a recomputation would not see the planted value
+        stobga.problem.terminal_mst_weight = OnceLock::from(42.0);
+        assert_eq!(stobga.terminal_mst_weight(), 42.0);
+    }
+
+    #[test]
+    fn terminal_mst_weight_shares_the_edge_costs() {
+        // solid walls between the terminals make any tree without
+        // Steiner points or corners infeasible, but not doubly so
+        let wall = |x: f32| {
+            Obstacle::new(INF, vec![(x, -1.0), (x + 0.2, -1.0), (x + 0.2, 2.0), (x, 2.0)])
+                .compute_bounds()
+        };
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)],
+            vec![wall(0.4), wall(1.4)],
+        );
+        let stobga = small_stobga(problem);
+        let empty = stobga.problem.spanning_tree(
+            &Chromosome::default(),
+            &mut HashMap::new(),
+            &stobga.config,
+            None,
+        );
+        assert_eq!(empty.total_weight, INF);
+        assert_eq!(stobga.terminal_mst_weight(), INF);
+    }
+
+    #[test]
+    fn classified_mst_edges() {
+        // the tree of trivial_mst, with (0, 1) as an obstacle corner and
+        // (0.5, 0.5) hanging off as a free Steiner point
+        let obstacle = Obstacle::new(2.0, vec![(0.0, 1.0), (-1.0, 1.0), (-1.0, 2.0)]).compute_bounds();
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (1.0, 1.0)], vec![obstacle]);
+        let mut graph = petgraph::graph::UnGraph::<Point, f32, u32>::new_undirected();
+        let a = graph.add_node((0.0, 0.0));
+        let b = graph.add_node((0.0, 1.0));
+        let c = graph.add_node((1.0, 1.0));
+        let d = graph.add_node((0.5, 0.5));
+        graph.add_edge(a, b, 1.0);
+        graph.add_edge(c, b, 1.0);
+        graph.add_edge(a, d, 0.7);
+        let mst = MinimumSpanningTree {
+            total_weight: 2.7,
+            fitness: 2.7,
+            graph,
+        };
+        let edges = mst.edges_classified(&problem);
+        assert_eq!(edges.len(), 3);
+        assert_eq!(
+            (edges[0].from_kind, edges[0].to_kind),
+            (NodeKind::Terminal, NodeKind::Corner)
+        );
+        assert_eq!(
+            (edges[1].from_kind, edges[1].to_kind),
+            (NodeKind::Terminal, NodeKind::Corner)
+        );
+        assert_eq!(
+            (edges[2].from_kind, edges[2].to_kind),
+            (NodeKind::Terminal, NodeKind::Steiner)
+        );
+        assert_eq!(edges[2].weight, 0.7);
+        assert!(!edges.iter().any(ClassifiedEdge::connects_terminals));
+    }
+
+    #[test]
+    fn describing_an_instance() {
+        let solid = Obstacle::new(INF, vec![(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)]).compute_bounds();
+        let weighted = Obstacle::new(2.0, vec![(5.0, 0.0), (6.0, 0.0), (5.0, 2.0)]).compute_bounds();
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (2.0, 2.0), (8.0, 4.0)],
+            vec![solid, weighted],
+        );
+        let stats = problem.describe();
+        assert_eq!(stats.terminal_count, 3);
+        assert_eq!(stats.obstacle_count, 2);
+        assert_eq!(stats.solid_obstacle_count, 1);
+        assert_eq!(stats.weighted_obstacle_count, 1);
+        assert!((stats.obstacle_area - 5.0).abs() < EPSILON);
+        assert_eq!(
+            (stats.bounds.min_x, stats.bounds.max_x, stats.bounds.min_y, stats.bounds.max_y),
+            (0.0, 8.0, 0.0, 4.0)
+        );
+        assert_eq!(stats.terminals_in_solid_obstacles, vec![1]);
+    }
+
+    #[test]
+    fn reseeding_from_a_saved_solution() {
+        let obstacle = Obstacle::new(INF, vec![(4.0, 4.0), (6.0, 4.0), (6.0, 6.0), (4.0, 6.0)]).compute_bounds();
+        let problem = SteinerProblem::new(
+            vec![(1.0, 1.0), (9.0, 1.0), (1.0, 9.0), (9.0, 9.0)],
+            vec![obstacle],
+        );
+        let config = StobgaConfig {
+            max_generations: Some(20),
+            ..StobgaConfig::default()
+        };
+        let mut stobga = StOBGA::<rand_pcg::Pcg32>::from_seed(0, problem.clone(), config.clone());
+        stobga.run();
+        let json = serde_json::to_string(&stobga.best_solution()).unwrap();
+        let saved: Solution = serde_json::from_str(&json).unwrap();
+
+        let mut fresh = StOBGA::<rand_pcg::Pcg32>::from_seed(1, problem, config);
+        fresh.seed_from_solution(&saved);
+        let lowest_weight = fresh.population_iter().map(|(_, weight)| weight).fold(INF, f32::min);
+        assert!(lowest_weight <= saved.total_weight + 1e-3);
+        assert!((fresh.population[0].weight() - lowest_weight).abs() < EPSILON);
+    }
+
+    #[test]
+    fn weighted_crossings_are_dashed_in_svg() {
+        let obstacle = Obstacle::new(2.0, vec![(1.0, -1.0), (2.0, -1.0), (2.0, 1.0), (1.0, 1.0)]).compute_bounds();
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)], vec![obstacle]);
+        let mut stobga = small_stobga(problem);
+        stobga.population[0] = Individual {
+            chromosome: Chromosome::new(vec![], vec![]),
+            minimum_spanning_tree: None,
+        };
+        stobga.build_msts();
+        let svg = stobga.instance_to_svg(0);
+        // only the edge from (0, 0) to (4, 0) runs through the obstacle
+        assert_eq!(svg.matches("<line").count(), 2);
+        assert_eq!(svg.matches("stroke-dasharray").count(), 1);
+        assert!(svg.contains("<line x1='0' y1='4000' x2='4000' y2='4000' style='stroke:#E0533A"));
+    }
+
+    #[test]
+    fn smaller_population() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)], vec![]);
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let mut stobga = StOBGA::new(rng, problem, 100, 1, 20, 20);
+        assert_eq!(stobga.population.len(), 100);
+        stobga.step();
+        assert_eq!(stobga.population.len(), 100);
+    }
+
+    #[test]
+    fn bottleneck_prefers_the_smaller_diameter() {
+        use crate::fitness::{Bottleneck, Fitness, TotalWeight};
+        let terminals = vec![(-1.5, 0.0), (0.0, 1.5), (1.5, 0.0), (0.0, -1.5)];
+        let problem = SteinerProblem::new(terminals.clone(), vec![]);
+        let tree = |nodes: &[Point], edges: &[(usize, usize, f32)]| {
+            let mut graph = petgraph::graph::UnGraph::<Point, f32, u32>::new_undirected();
+            let indices: Vec<_> = nodes.iter().map(|&p| graph.add_node(p)).collect();
+            for &(a, b, weight) in edges {
+                graph.add_edge(indices[a], indices[b], weight);
+            }
+            MinimumSpanningTree {
+                total_weight: 6.0,
+                fitness: 6.0,
+                graph,
+            }
+        };
+        // both trees weigh 6: a star around a Steiner point in the middle
+        // and a path visiting the terminals one after the other
+        let mut star_nodes = terminals.clone();
+        star_nodes.push((0.0, 0.0));
+        let star = tree(&star_nodes, &[(4, 0, 1.5), (4, 1, 1.5), (4, 2, 1.5), (4, 3, 1.5)]);
+        let path = tree(&terminals, &[(0, 1, 2.0), (1, 2, 2.0), (2, 3, 2.0)]);
+        assert_eq!(Bottleneck.evaluate(&star, &problem), 3.0);
+        assert_eq!(Bottleneck.evaluate(&path, &problem), 6.0);
+        assert_eq!(
+            TotalWeight.evaluate(&star, &problem),
+            TotalWeight.evaluate(&path, &problem)
+        );
+    }
+
+    #[test]
+    fn iterating_over_generations() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)], vec![]);
+        let mut stobga = small_stobga(problem);
+        let reports: Vec<_> = stobga.generations().take(5).collect();
+        assert_eq!(
+            reports.iter().map(|report| report.generation).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        let reports: Vec<_> = stobga
+            .generations_until(crate::termination::MaxGenerations(8))
+            .collect();
+        assert_eq!(reports.last().unwrap().generation, 8);
+        assert_eq!(reports.len(), 3);
+    }
+
+    #[test]
+    fn finalize_keeps_steiner_points_out_of_solid_obstacles() {
+        // the Fermat point of the terminals lies at about (2, 1.15), in a
+        // small solid obstacle that is cheap to cross with the penalty
+        let obstacle = Obstacle::new(INF, vec![(1.9, 1.05), (2.1, 1.05), (2.1, 1.25), (1.9, 1.25)]).compute_bounds();
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)], vec![obstacle]);
+        let config = StobgaConfig {
+            solid_penalty: Some(1.0),
+            ..StobgaConfig::default()
+        };
+        let mut stobga = StOBGA::with_config(rand_pcg::Pcg32::seed_from_u64(0), problem, config);
+        stobga.population[0] = Individual {
+            chromosome: Chromosome::new(vec![(2.0, 0.8)], vec![]),
+            minimum_spanning_tree: None,
+        };
+        stobga.finalize();
+        let points: Vec<Point> = stobga.population[0].chromosome.steiner_points().collect();
+        assert_eq!(points, vec![(2.0, 0.8)]);
+    }
+
+    #[test]
+    fn fitness_histogram_of_a_handcrafted_population() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)], vec![]);
+        let mut stobga = small_stobga(problem);
+        // weights 49, 48, ..., 0, the lightest individual comes last
+        for (index, individual) in stobga.population.iter_mut().enumerate() {
+            individual.minimum_spanning_tree.as_mut().unwrap().total_weight = (49 - index) as f32;
+        }
+        let histogram = stobga.fitness_histogram(5);
+        assert_eq!(histogram, vec![10; 5]);
+        assert_eq!(histogram.iter().sum::<usize>(), stobga.population.len());
+        assert_eq!(stobga.fitness_histogram(1), vec![50]);
+        assert!(stobga.fitness_histogram(0).is_empty());
+
+        stobga.population[3].minimum_spanning_tree.as_mut().unwrap().total_weight = -1.0;
+        let histogram = stobga.fitness_histogram(7);
+        assert!(histogram[0] >= 1);
+        assert_eq!(histogram.iter().sum::<usize>(), 50);
+    }
+
+    #[test]
+    fn checking_solutions() {
+        let obstacle = Obstacle::new(INF, vec![(1.0, -1.0), (2.0, -1.0), (2.0, 1.0), (1.0, 1.0)]).compute_bounds();
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)], vec![obstacle]);
+        let good = Solution {
+            steiner_points: vec![],
+            included_corners: vec![],
+            edges: vec![((0.0, 0.0), (2.0, 3.0)), ((2.0, 3.0), (4.0, 0.0))],
+            total_weight: 0.0,
+            feasible: true,
+        };
+        let check = problem.check_solution(&good);
+        assert!(check.feasible());
+        assert!((check.total_weight - 2.0 * 13.0f32.sqrt()).abs() < 1e-4);
+
+        // through the obstacle and leaving out the top terminal
+        let broken = Solution {
+            edges: vec![((0.0, 0.0), (4.0, 0.0))],
+            ..good.clone()
+        };
+        let check = problem.check_solution(&broken);
+        assert!(!check.feasible());
+        assert_eq!(check.missing_terminals, vec![2]);
+        assert!(!check.connected);
+        assert_eq!(check.solid_crossings, vec![((0.0, 0.0), (4.0, 0.0))]);
+
+        // all terminals present, but in two parts
+        let split = Solution {
+            edges: vec![((0.0, 0.0), (0.0, 1.0)), ((4.0, 0.0), (2.0, 3.0))],
+            ..good
+        };
+        let check = problem.check_solution(&split);
+        assert!(check.missing_terminals.is_empty());
+        assert!(!check.connected);
+        assert!(!check.feasible());
+
+        // connected, but with a cycle
+        let cycle = Solution {
+            edges: vec![
+                ((0.0, 0.0), (2.0, 3.0)),
+                ((2.0, 3.0), (4.0, 0.0)),
+                ((4.0, 0.0), (2.0, 3.0)),
+            ],
+            ..split
+        };
+        let check = problem.check_solution(&cycle);
+        assert!(check.connected);
+        assert!(check.cyclic);
+        assert!(!check.feasible());
+    }
+
+    #[test]
+    fn pt_round_trips() {
+        let pt = Pt::new(1.5, -2.25);
+        assert_eq!(Pt::from(to_point(to_graph(pt))), pt);
+        assert_eq!(Pt::from(to_graph(pt)), pt);
+        assert_eq!(Point::from(pt), (1.5, -2.25));
+        assert_eq!(to_graph((1.5, -2.25)), OPoint::from(pt));
+    }
+
+    #[test]
+    fn greedy_steiner_on_a_triangle() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)], vec![]);
+        let solution = greedy_steiner(&problem);
+        assert_eq!(solution.steiner_points.len(), 1);
+        let fermat = (2.0, 2.0 / 3.0f32.sqrt());
+        assert!(euclidean_distance(solution.steiner_points[0], fermat) < 1e-2);
+        assert!(solution.total_weight < problem.evaluate(&Chromosome::new(vec![], vec![])));
+        // no randomness involved
+        assert_eq!(greedy_steiner(&problem).steiner_points, solution.steiner_points);
+    }
+
+    #[test]
+    fn corners_near_terminals_are_preferred() {
+        // one obstacle between the terminals and one far away from them
+        let between = Obstacle::new(INF, vec![(4.0, -1.0), (6.0, -1.0), (6.0, 1.0), (4.0, 1.0)]).compute_bounds();
+        let far = Obstacle::new(INF, vec![(4.0, 30.0), (6.0, 30.0), (6.0, 32.0), (4.0, 32.0)]).compute_bounds();
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (10.0, 0.0)], vec![between, far]);
+        let probabilities = problem.corner_probabilities(8);
+        let mut rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let (mut uniform, mut biased) = ([0; 2], [0; 2]);
+        for _ in 0..1000 {
+            for (counts, probabilities) in [(&mut uniform, None), (&mut biased, Some(&probabilities[..]))] {
+                let individual = Individual::random_corners(8, probabilities, &mut rng);
+                for corner in individual.chromosome.included_corners.iter() {
+                    counts[corner / 4] += 1;
+                }
+            }
+        }
+        assert!(biased[0] > uniform[0]);
+        assert!(biased[1] < uniform[1]);
+        assert!(biased[0] > 10 * biased[1]);
+
+        let config = StobgaConfig {
+            init_strategy: InitStrategy::CornersNearTerminals,
+            ..StobgaConfig::default()
+        };
+        let stobga = StOBGA::with_config(rand_pcg::Pcg32::seed_from_u64(0), problem, config);
+        assert_eq!(stobga.population.len(), POPULATION_SIZE);
+    }
+
+    #[test]
+    fn edge_evaluations_grow_with_the_genome() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (10.0, 0.0), (5.0, 8.0)], vec![]);
+        let mut stobga = small_stobga(problem);
+        let mut evaluate = |steiner_points: Vec<Point>| {
+            stobga.population[0] = Individual {
+                chromosome: Chromosome::new(steiner_points, vec![]),
+                minimum_spanning_tree: None,
+            };
+            let (functions, edges) = (stobga.function_evaluations, stobga.edge_evaluations);
+            stobga.build_msts();
+            (stobga.function_evaluations - functions, stobga.edge_evaluations - edges)
+        };
+        // points off any grid the initial population could have used
+        let small = evaluate((0..2).map(|i| (1.234 + i as f32, 0.567)).collect());
+        let large = evaluate((0..20).map(|i| (1.234 + i as f32 * 0.3, 2.567)).collect());
+        assert_eq!(small.0, 1);
+        assert_eq!(large.0, 1);
+        assert!(large.1 > 10 * small.1);
+    }
+
+    #[test]
+    fn relaxing_a_fixed_topology() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)], vec![]);
+        // the left and the right terminals share a Steiner point each
+        let (left, right) = ((0.4, 0.3), (0.6, 0.7));
+        let mut topology = Graph::new();
+        topology.add_edge_from_points((0.0, 0.0), left, 0.0);
+        topology.add_edge_from_points((0.0, 1.0), left, 0.0);
+        topology.add_edge_from_points((1.0, 0.0), right, 0.0);
+        topology.add_edge_from_points((1.0, 1.0), right, 0.0);
+        topology.add_edge_from_points(left, right, 0.0);
+        let (points, cost) = optimize_fixed_topology(&topology, &problem);
+        let offset = 0.5 / 3.0f32.sqrt();
+        assert_eq!(points.len(), 2);
+        assert!(euclidean_distance(points[0], (offset, 0.5)) < 1e-3);
+        assert!(euclidean_distance(points[1], (1.0 - offset, 0.5)) < 1e-3);
+        assert!((cost - (1.0 + 3.0f32.sqrt())).abs() < 1e-3);
+    }
+
+    #[test]
+    fn no_steiner_points_in_keepout() {
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (10.0, 0.0), (5.0, 8.0)], vec![]);
+        let keepout = Bounds {
+            min_x: 3.0,
+            max_x: 7.0,
+            min_y: 1.0,
+            max_y: 5.0,
+        };
+        let config = StobgaConfig {
+            steiner_keepout: vec![keepout.clone()],
+            max_generations: Some(20),
+            ..StobgaConfig::default()
+        };
+        let mut stobga = StOBGA::with_config(rand_pcg::Pcg32::seed_from_u64(0), problem, config);
+        // edges through the keep-out cost their plain length
+        let through = stobga.compute_distance(to_graph((5.0, 0.0)), to_graph((5.0, 8.0)));
+        assert!((through - 8.0).abs() < EPSILON);
+        stobga.run();
+        let inside = |p: Point| {
+            keepout.min_x <= p.0 && p.0 <= keepout.max_x && keepout.min_y <= p.1 && p.1 <= keepout.max_y
+        };
+        for individual in &stobga.population {
+            assert!(!individual.chromosome.steiner_points().any(inside));
+        }
+        // the optimal Steiner point would be inside, so the best tree has to
+        // make do with points around it
+        let best = stobga.best_solution();
+        assert!(!best.steiner_points.iter().any(|&p| inside(p)));
+    }
+
+    #[test]
+    fn mst_building_dominates_phase_timings() {
+        let mut rng = rand_pcg::Pcg32::seed_from_u64(3);
+        let terminals: Vec<Point> = (0..30)
+            .map(|_| (rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0)))
+            .collect();
+        let obstacles = (0..16)
+            .map(|i| {
+                let (x, y) = (5.0 + 24.0 * (i % 4) as f32, 5.0 + 24.0 * (i / 4) as f32);
+                Obstacle::new(
+                    if i % 2 == 0 { INF } else { 1.5 },
+                    vec![(x, y), (x + 8.0, y), (x + 8.0, y + 15.0), (x, y + 15.0)],
+                )
+                .compute_bounds()
+            })
+            .collect();
+        let problem = SteinerProblem::new(terminals, obstacles);
+        let config = StobgaConfig {
+            population_size: 100,
+            t2: 10,
+            t3: 10,
+            ..StobgaConfig::default()
+        };
+        let mut stobga = StOBGA::with_config(rng, problem, config);
+        assert_eq!(stobga.phase_timings(), PhaseTimings::default());
+        let start = Instant::now();
+        for _ in 0..3 {
+            stobga.step();
+        }
+        let elapsed = start.elapsed();
+        let timings = stobga.phase_timings();
+        assert!(timings.mst_building > Duration::ZERO);
+        // the phases do not overlap, trees built during crossover or
+        // mutation only count for MST building
+        let total = timings.selection
+            + timings.crossover
+            + timings.mutation
+            + timings.mst_building
+            + timings.sorting;
+        assert!(total <= elapsed, "{:?}", timings);
+        // with this many obstacles the trees take over a hundred times as
+        // long as the rest, so a tenfold margin leaves room for noise
+        let rest = timings.selection + timings.crossover + timings.mutation + timings.sorting;
+        assert!(timings.mst_building > 10 * rest, "{:?}", timings);
+    }
+
+    #[test]
+    fn population_grid_of_the_best_four() {
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (1.0, 0.0), (0.5, 1.0), (0.0, 1.0)],
+            vec![Obstacle::new(INF, vec![(0.4, 0.4), (0.6, 0.4), (0.6, 0.6), (0.4, 0.6)]).compute_bounds()],
+        );
+        let mut stobga =
+            StOBGA::with_config(rand_pcg::Pcg32::seed_from_u64(0), problem, StobgaConfig::default());
+        stobga.step();
+        let svg = stobga.population_grid_svg(2, 4);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<g transform").count(), 4);
+        assert_eq!(svg.matches("<text").count(), 4);
+        // the best individual comes first
+        let best = stobga.population[0].minimum_spanning_tree.as_ref().unwrap().total_weight;
+        assert!(svg.contains(&format!("<g transform='translate(0,0)'>{}", stobga.instance_to_svg(0))));
+        assert!(svg.contains(&format!(">{}</text>", best)));
+
+        // far from the origin the cells are only as large as the instance
+        let problem = SteinerProblem::new(vec![(10.0, 20.0), (11.0, 20.0), (10.5, 21.0)], vec![]);
+        let mut stobga = small_stobga(problem);
+        stobga.step();
+        let svg = stobga.population_grid_svg(2, 3);
+        assert!(svg.starts_with("<svg width='2000px' height='2200px'>"));
+        assert!(svg.contains("<g transform='translate(-10000,0)'>"));
+        assert!(svg.contains("<g transform='translate(-9000,0)'>"));
+        assert!(svg.contains("<g transform='translate(-10000,1100)'>"));
+        assert_eq!(svg.matches("<text x='10000'").count(), 3);
+    }
+
+    #[test]
+    fn init_fractions_size_the_buckets() {
+        let config = StobgaConfig {
+            population_size: 500,
+            ..StobgaConfig::default()
+        }
+        .init_fractions(0.01, 0.1, 0.1);
+        assert_eq!(config.init_counts(), Ok((5, 50, 50)));
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (1.0, 0.0), (0.5, 1.0)], vec![]);
+        let stobga = StOBGA::with_config(rand_pcg::Pcg32::seed_from_u64(0), problem, config);
+        assert_eq!(stobga.population.len(), 500);
+    }
+
+    #[test]
+    fn init_fractions_exceeding_the_population() {
+        for fractions in [(0.5, 0.5, 0.1), (-0.1, 0.5, 0.0), (f32::NAN, 0.0, 0.0)] {
+            let config = StobgaConfig::default().init_fractions(fractions.0, fractions.1, fractions.2);
+            assert!(matches!(config.init_counts(), Err(SolverError::InitFractions { .. })));
+        }
+        // rounding each half up would ask for four of three individuals
+        let config = StobgaConfig {
+            population_size: 3,
+            tournament_size: 2,
+            ..StobgaConfig::default()
+        }
+        .init_fractions(0.5, 0.5, 0.0);
+        assert_eq!(config.init_counts(), Ok((2, 1, 0)));
+        let problem = SteinerProblem::new(vec![(0.0, 0.0), (1.0, 0.0), (0.5, 1.0)], vec![]);
+        let stobga = StOBGA::try_with_config(rand_pcg::Pcg32::seed_from_u64(0), problem, config);
+        assert_eq!(stobga.unwrap().population.len(), 3);
+    }
+
+    #[test]
+    fn walled_off_terminals_give_an_infeasible_tree() {
+        let wall = |x: f32| {
+            Obstacle::new(INF, vec![(x, -1.0), (x + 0.2, -1.0), (x + 0.2, 2.0), (x, 2.0)])
+                .compute_bounds()
+        };
+        // three terminals separated by two solid walls, only going around
+        // their ends over the corners would connect them
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)],
+            vec![wall(0.4), wall(1.4)],
+        );
+        let chromosome = Chromosome::new(vec![(1.0, 0.5)], vec![]);
+        let mst = problem.spanning_tree(&chromosome, &mut HashMap::new(), &StobgaConfig::default(), None);
+        // two impassable edges, which used to sum up to 2 * INF
+        assert_eq!(mst.graph.edge_weights().filter(|&&weight| weight >= INF).count(), 2);
+        assert_eq!(mst.total_weight, INF);
+        assert_eq!(mst.fitness, INF);
+        assert_eq!(problem.evaluate(&chromosome), INF);
+    }
+
+    #[test]
+    fn statistics_cost_no_extra_evaluations() {
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (0.0, 5.0), (4.0, 5.0)],
+            vec![],
+        );
+        let config = StobgaConfig {
+            population_size: 50,
+            t2: 10,
+            t3: 10,
+            ..StobgaConfig::default()
+        };
+        let mut stobga = StOBGA::with_config(rand_pcg::Pcg32::seed_from_u64(0), problem, config);
+        let before = stobga.operator_stats().clone();
+        let evaluations = stobga.function_evaluations;
+        for _ in 0..10 {
+            stobga.crossover(0, 1);
+        }
+        assert_eq!(stobga.function_evaluations, evaluations);
+        stobga.mutate_child_buffer();
+        let stats = stobga.operator_stats();
+        let applied = |op| stats.get(op).applications - before.get(op).applications;
+        assert_eq!(applied(Operator::Crossover), 10);
+        // flip-move children are only evaluated once mutated, the others
+        // also before
+        assert_eq!(
+            stobga.function_evaluations - evaluations,
+            applied(Operator::FlipMove) + 2 * (applied(Operator::AddSteiner) + applied(Operator::RemoveSteiner))
+        );
+    }
+
+    #[test]
+    fn single_child_keeps_the_lighter_child() {
+        let problem = SteinerProblem::new(
+            vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0), (0.0, 5.0), (4.0, 5.0)],
+            vec![],
+        );
+        let config = StobgaConfig {
+            population_size: 50,
+            t2: 10,
+            t3: 10,
+            single_child: true,
+            ..StobgaConfig::default()
+        };
+        let mut stobga = StOBGA::with_config(rand_pcg::Pcg32::seed_from_u64(0), problem, config);
+        for parents in [(0, 1), (2, 3), (10, 20)] {
+            let mut rng = stobga.random_generator.clone();
+            let (child_1, child_2) = stobga.config.crossover.recombine(
+                &stobga.population[parents.0].chromosome,
+                &stobga.population[parents.1].chromosome,
+                &stobga.problem,
+                &mut rng,
+            );
+            let lighter = f32::min(stobga.problem.evaluate(&child_1), stobga.problem.evaluate(&child_2));
+            let evaluations = stobga.function_evaluations;
+            stobga.crossover(parents.0, parents.1);
+            // both children are evaluated to find the lighter one
+            assert_eq!(stobga.function_evaluations - evaluations, 2);
+            assert_eq!(stobga.child_buffer.len(), 1);
+            assert_eq!(stobga.child_buffer[0].weight(), lighter);
+            stobga.child_buffer.clear();
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! an archive of the trade-offs between tree length and the amount of
+//! Steiner points found during a run. It only observes the population,
+//! selection is still driven by weight alone.
+
+use crate::Solution;
+
+/// the objectives of a solution: its weight and its amount of Steiner
+/// points and obstacle corners, both to be minimized.
+fn objectives(solution: &Solution) -> (f32, usize) {
+    (
+        solution.total_weight,
+        solution.steiner_points.len() + solution.included_corners.len(),
+    )
+}
+
+/// whether `a` is at least as good as `b` in both objectives and better in
+/// one.
+fn dominates(a: (f32, usize), b: (f32, usize)) -> bool {
+    a.0 <= b.0 && a.1 <= b.1 && (a.0 < b.0 || a.1 < b.1)
+}
+
+/// a set of solutions none of which dominates another.
+#[derive(Debug, Clone, Default)]
+pub struct ParetoArchive {
+    solutions: Vec<Solution>,
+}
+
+impl ParetoArchive {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// whether a solution with these objectives would enter the archive,
+    /// i.e. no archived solution dominates or equals it.
+    pub fn accepts(&self, total_weight: f32, steiner_count: usize) -> bool {
+        let candidate = (total_weight, steiner_count);
+        !self.solutions.iter().any(|solution| {
+            let archived = objectives(solution);
+            archived == candidate || dominates(archived, candidate)
+        })
+    }
+
+    /// adds `solution` unless it is dominated, dropping the solutions it
+    /// dominates. Returns whether it was added.
+    pub fn insert(&mut self, solution: Solution) -> bool {
+        let candidate = objectives(&solution);
+        if !self.accepts(candidate.0, candidate.1) {
+            return false;
+        }
+        self.solutions
+            .retain(|archived| !dominates(candidate, objectives(archived)));
+        self.solutions.push(solution);
+        true
+    }
+
+    /// the archived solutions, by increasing amount of Steiner points.
+    pub fn solutions(&self) -> Vec<Solution> {
+        let mut solutions = self.solutions.clone();
+        solutions.sort_by_key(|solution| objectives(solution).1);
+        solutions
+    }
+}
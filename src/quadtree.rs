@@ -0,0 +1,139 @@
+//! a quadtree over a set of points, answering nearest neighbor queries
+//! without looking at every point.
+
+use std::sync::OnceLock;
+
+use crate::geometry::{euclidean_distance, Bounds};
+use crate::Point;
+
+/// the most points a leaf holds before it gets split.
+const LEAF_CAPACITY: usize = 8;
+/// the deepest a leaf may lie, so that many (nearly) coincident points do
+/// not split forever.
+const MAX_DEPTH: usize = 24;
+
+enum Node {
+    Leaf(Vec<Point>),
+    Inner(Vec<(Bounds, Node)>),
+}
+
+/// built once from a set of points. The cells are shrunk to the bounds of
+/// the points they hold, so empty space is pruned early.
+pub(crate) struct QuadTree {
+    root: Option<Node>,
+}
+
+/// the distance from `p` to the closest point of the box, 0 inside of it.
+fn box_distance(p: Point, bounds: &Bounds) -> f32 {
+    let dx = (bounds.min_x - p.0).max(p.0 - bounds.max_x).max(0.0);
+    let dy = (bounds.min_y - p.1).max(p.1 - bounds.max_y).max(0.0);
+    (dx * dx + dy * dy).sqrt()
+}
+
+fn build(points: Vec<Point>, bounds: &Bounds, depth: usize) -> Node {
+    if points.len() <= LEAF_CAPACITY || depth >= MAX_DEPTH {
+        return Node::Leaf(points);
+    }
+    let center = (
+        (bounds.min_x + bounds.max_x) / 2.0,
+        (bounds.min_y + bounds.max_y) / 2.0,
+    );
+    let mut quadrants = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+    for p in points {
+        let index = (p.0 >= center.0) as usize + 2 * (p.1 >= center.1) as usize;
+        quadrants[index].push(p);
+    }
+    Node::Inner(
+        quadrants
+            .into_iter()
+            .filter(|quadrant| !quadrant.is_empty())
+            .map(|quadrant| {
+                let bounds = Bounds::of_points(&quadrant);
+                let node = build(quadrant, &bounds, depth + 1);
+                (bounds, node)
+            })
+            .collect(),
+    )
+}
+
+impl QuadTree {
+    pub(crate) fn new(points: impl IntoIterator<Item = Point>) -> Self {
+        let points: Vec<Point> = points.into_iter().collect();
+        if points.is_empty() {
+            return Self { root: None };
+        }
+        let bounds = Bounds::of_points(&points);
+        Self {
+            root: Some(build(points, &bounds, 0)),
+        }
+    }
+
+    /// the point closest to `p` and its distance, `None` if there are no
+    /// points.
+    pub(crate) fn nearest(&self, p: Point) -> Option<(Point, f32)> {
+        fn visit(node: &Node, p: Point, best: &mut Option<(Point, f32)>) {
+            match node {
+                Node::Leaf(points) => {
+                    for &q in points {
+                        let distance = euclidean_distance(p, q);
+                        if best.is_none_or(|(_, d)| distance < d) {
+                            *best = Some((q, distance));
+                        }
+                    }
+                }
+                Node::Inner(children) => {
+                    let mut children: Vec<(f32, &Node)> = children
+                        .iter()
+                        .map(|(bounds, child)| (box_distance(p, bounds), child))
+                        .collect();
+                    children.sort_by(|a, b| a.0.total_cmp(&b.0));
+                    for (distance, child) in children {
+                        if best.is_none_or(|(_, d)| distance < d) {
+                            visit(child, p, best);
+                        }
+                    }
+                }
+            }
+        }
+        let mut best = None;
+        if let Some(root) = &self.root {
+            visit(root, p, &mut best);
+        }
+        best
+    }
+}
+
+/// a [QuadTree] over points that change now and then, built on the first
+/// query after each change. Clones start out empty and any two compare
+/// equal, so the tree never sets apart otherwise equal owners.
+#[derive(Default)]
+pub(crate) struct LazyQuadTree(OnceLock<QuadTree>);
+
+impl LazyQuadTree {
+    /// the point closest to `p` and its distance, building the tree from
+    /// `points` first if there is none.
+    pub(crate) fn nearest<I: IntoIterator<Item = Point>>(
+        &self,
+        points: impl FnOnce() -> I,
+        p: Point,
+    ) -> Option<(Point, f32)> {
+        self.0.get_or_init(|| QuadTree::new(points())).nearest(p)
+    }
+
+    /// drops the tree, to be called whenever the points change.
+    pub(crate) fn clear(&mut self) {
+        self.0.take();
+    }
+}
+
+impl Clone for LazyQuadTree {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl PartialEq for LazyQuadTree {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rand::Rng;
+
+use itertools::Itertools;
+
+use crate::{
+    geometry::{euclidean_distance, polygon_area, Bounds},
+    termination::Termination,
+    Chromosome, Solution, StOBGA, SteinerProblem, INF,
+};
+
+/// key figures of an instance, see [SteinerProblem::describe].
+#[derive(Debug, Clone)]
+pub struct InstanceStats {
+    pub terminal_count: usize,
+    pub obstacle_count: usize,
+    pub solid_obstacle_count: usize,
+    pub weighted_obstacle_count: usize,
+    /// the summed area of all obstacles, overlaps counted twice
+    pub obstacle_area: f32,
+    /// see [SteinerProblem::bounds]
+    pub bounds: Bounds,
+    /// the indices of the terminals lying within a solid obstacle, which
+    /// no tree can reach without crossing it
+    pub terminals_in_solid_obstacles: Vec<usize>,
+}
+
+impl std::fmt::Display for InstanceStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "terminals: {}", self.terminal_count)?;
+        writeln!(
+            f,
+            "obstacles: {} ({} solid, {} weighted)",
+            self.obstacle_count, self.solid_obstacle_count, self.weighted_obstacle_count
+        )?;
+        writeln!(f, "obstacle area: {}", self.obstacle_area)?;
+        writeln!(
+            f,
+            "bounds: x {}..{}, y {}..{}",
+            self.bounds.min_x, self.bounds.max_x, self.bounds.min_y, self.bounds.max_y
+        )?;
+        write!(
+            f,
+            "terminals in solid obstacles: {:?}",
+            self.terminals_in_solid_obstacles
+        )
+    }
+}
+
+impl SteinerProblem {
+    /// counts and sizes of the instance, to check it before a run.
+    pub fn describe(&self) -> InstanceStats {
+        let solid_obstacle_count = self
+            .obstacles
+            .iter()
+            .filter(|obstacle| obstacle.weight == INF)
+            .count();
+        InstanceStats {
+            terminal_count: self.terminals.len(),
+            obstacle_count: self.obstacles.len(),
+            solid_obstacle_count,
+            weighted_obstacle_count: self.obstacles.len() - solid_obstacle_count,
+            obstacle_area: self
+                .obstacles
+                .iter()
+                .map(|obstacle| polygon_area(&obstacle.points))
+                .sum(),
+            bounds: self.bounds(),
+            terminals_in_solid_obstacles: self
+                .terminals
+                .iter()
+                .positions(|&terminal| self.coordinates_in_solid_obstacle(terminal))
+                .collect(),
+        }
+    }
+}
+
+/// a snapshot of the state of a run after a generation.
+#[derive(Debug, Clone)]
+pub struct GenerationReport {
+    pub generation: usize,
+    pub best_weight: f32,
+    pub average_weight: f32,
+    pub function_evaluations: u64,
+    /// see [StOBGA::diversity]
+    pub diversity: f32,
+    /// seconds since the solver was created, if a clock is available
+    pub elapsed: Option<f32>,
+}
+
+/// the reports as comma separated values with a header line, one row per
+/// report. The seconds are left empty where no clock is available.
+pub fn history_to_csv(history: &[GenerationReport]) -> String {
+    let mut csv = String::from("generation,best_weight,average_weight,function_evaluations,seconds\n");
+    for report in history {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            report.generation,
+            report.best_weight,
+            report.average_weight,
+            report.function_evaluations,
+            report.elapsed.map(|s| s.to_string()).unwrap_or_default()
+        ));
+    }
+    csv
+}
+
+/// the generations of a run, one [StOBGA::step] per item, see
+/// [StOBGA::generations]. Once the termination criterion fires the solver
+/// is finalized and the iterator ends.
+pub struct Generations<'a, R: Rng, T: Termination<R>> {
+    stobga: &'a mut StOBGA<R>,
+    termination: T,
+    done: bool,
+}
+
+impl<R: Rng, T: Termination<R>> Iterator for Generations<'_, R, T> {
+    type Item = GenerationReport;
+
+    fn next(&mut self) -> Option<GenerationReport> {
+        if self.done {
+            return None;
+        }
+        self.stobga.step();
+        let report = self.stobga.report();
+        if self.termination.should_stop(self.stobga) {
+            self.done = true;
+            self.stobga.finalize();
+        }
+        Some(report)
+    }
+}
+
+/// the time spent in each phase of the generations so far, see
+/// [StOBGA::phase_timings]. All zero where there is no clock (wasm).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    /// tournaments picking parents and the individuals to die
+    pub selection: Duration,
+    /// recombining parents, without building the children's trees
+    pub crossover: Duration,
+    /// mutating children, without rebuilding their trees
+    pub mutation: Duration,
+    /// building minimum spanning trees, wherever it happens
+    pub mst_building: Duration,
+    /// ordering the population by fitness
+    pub sorting: Duration,
+}
+
+/// the variation operators of the genetic algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    FlipMove,
+    AddSteiner,
+    RemoveSteiner,
+    Crossover,
+}
+
+/// how often an operator was applied and how often that yielded an
+/// individual lighter than the one(s) it started from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperatorCount {
+    pub applications: u64,
+    pub improvements: u64,
+}
+
+/// the [OperatorCount]s of a run, see [StOBGA::operator_stats]. A crossover
+/// improves if a child is lighter than both parents after its mutation,
+/// since the children are only evaluated then. A mutation improves if the
+/// child is lighter than before, which for flip-move means lighter than
+/// its parents.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperatorStats {
+    pub flip_move: OperatorCount,
+    pub add_steiner: OperatorCount,
+    pub remove_steiner: OperatorCount,
+    pub crossover: OperatorCount,
+}
+
+impl OperatorStats {
+    pub fn get(&self, operator: Operator) -> OperatorCount {
+        match operator {
+            Operator::FlipMove => self.flip_move,
+            Operator::AddSteiner => self.add_steiner,
+            Operator::RemoveSteiner => self.remove_steiner,
+            Operator::Crossover => self.crossover,
+        }
+    }
+
+    pub(crate) fn record(&mut self, operator: Operator, improved: bool) {
+        let count = match operator {
+            Operator::FlipMove => &mut self.flip_move,
+            Operator::AddSteiner => &mut self.add_steiner,
+            Operator::RemoveSteiner => &mut self.remove_steiner,
+            Operator::Crossover => &mut self.crossover,
+        };
+        count.applications += 1;
+        if improved {
+            count.improvements += 1;
+        }
+    }
+}
+
+/// the outcome of a run, see [StOBGA::summary].
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub best_weight: f32,
+    pub generations: usize,
+    /// see [StOBGA::best_found_generation]
+    pub best_found_generation: usize,
+    pub function_evaluations: u64,
+    /// seconds since the solver was created, if a clock is available
+    pub elapsed: Option<f32>,
+    /// the free Steiner points and obstacle corners of the best individual
+    pub steiner_count: usize,
+    pub feasible: bool,
+    /// how much lighter the best tree is than the minimum spanning tree of
+    /// the terminals alone, relative to the latter
+    pub gap: f32,
+    /// see [Solution::bbox_fill_ratio]
+    pub bbox_fill_ratio: f32,
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "best weight {} after {} generations (found in generation {}) and {} function evaluations",
+            self.best_weight, self.generations, self.best_found_generation, self.function_evaluations
+        )?;
+        if let Some(elapsed) = self.elapsed {
+            write!(f, " in {:.2}s", elapsed)?;
+        }
+        write!(
+            f,
+            ", {} Steiner points, {}, {:.2}% below the terminal MST, spanning {:.2}% of the bounds",
+            self.steiner_count,
+            if self.feasible {
+                "feasible"
+            } else {
+                "infeasible"
+            },
+            self.gap * 100.0,
+            self.bbox_fill_ratio * 100.0
+        )
+    }
+}
+
+/// the arithmetic mean, summed up in double precision so that equal values
+/// average to exactly that value.
+fn mean(values: impl Iterator<Item = f32>) -> f32 {
+    let (sum, len) = values.fold((0.0f64, 0usize), |(sum, len), value| {
+        (sum + value as f64, len + 1)
+    });
+    (sum / len as f64) as f32
+}
+
+impl<R: Rng> StOBGA<R> {
+    /// a measure in `[0, 1]` of how different the individuals are: the mean
+    /// of the normalized mean pairwise difference in gene counts and the
+    /// normalized spread of the individuals' gene centroids. A population of
+    /// identical individuals has a diversity of 0.
+    pub fn diversity(&self) -> f32 {
+        let mut counts = Vec::new();
+        let mut centroids = Vec::new();
+        for individual in &self.population {
+            let genes: Vec<_> = individual
+                .chromosome
+                .steiner_points()
+                .chain(
+                    individual
+                        .chromosome
+                        .included_corners()
+                        .map(|corner| self.problem.obstacle_corners[corner]),
+                )
+                .collect();
+            counts.push(genes.len() as f32);
+            if !genes.is_empty() {
+                centroids.push((
+                    mean(genes.iter().map(|p| p.0)),
+                    mean(genes.iter().map(|p| p.1)),
+                ));
+            }
+        }
+
+        // mean absolute difference of all pairs, through the sorted counts
+        counts.sort_by(|a, b| a.total_cmp(b));
+        let n = counts.len() as f32;
+        let max_count = counts.last().copied().unwrap_or(0.0);
+        let count_part = if n > 1.0 && max_count > 0.0 {
+            let sum: f32 = counts
+                .iter()
+                .enumerate()
+                .map(|(i, count)| (2.0 * i as f32 - n + 1.0) * count)
+                .sum();
+            (sum / (n * (n - 1.0) / 2.0) / max_count).min(1.0)
+        } else {
+            0.0
+        };
+
+        let bounds = &self.problem.bounds;
+        let half_diagonal =
+            euclidean_distance((bounds.min_x, bounds.min_y), (bounds.max_x, bounds.max_y)) / 2.0;
+        let spatial_part = if centroids.len() > 1 && half_diagonal > 0.0 {
+            let center = (
+                mean(centroids.iter().map(|p| p.0)),
+                mean(centroids.iter().map(|p| p.1)),
+            );
+            let spread = mean(centroids.iter().map(|&p| euclidean_distance(p, center)));
+            (spread / half_diagonal).min(1.0)
+        } else {
+            0.0
+        };
+
+        (count_part + spatial_part) / 2.0
+    }
+
+    /// how many individuals fall into each of `bins` equally wide ranges of
+    /// the total weight, from the lowest weight in the first bin to the
+    /// highest in the last. If all weights are equal they all land in the
+    /// first bin.
+    pub fn fitness_histogram(&self, bins: usize) -> Vec<usize> {
+        let mut histogram = vec![0; bins];
+        if bins == 0 {
+            return histogram;
+        }
+        let weights: Vec<f32> = self.population_iter().map(|(_, weight)| weight).collect();
+        let min = weights.iter().copied().fold(f32::INFINITY, f32::min) as f64;
+        let max = weights.iter().copied().fold(f32::NEG_INFINITY, f32::max) as f64;
+        for weight in weights {
+            let bin = if max > min {
+                ((weight as f64 - min) / (max - min) * bins as f64) as usize
+            } else {
+                0
+            };
+            histogram[bin.min(bins - 1)] += 1;
+        }
+        histogram
+    }
+
+    /// how often each operator was applied and improved on its input,
+    /// counted since the solver was created.
+    pub fn operator_stats(&self) -> &OperatorStats {
+        &self.operator_stats
+    }
+
+    /// the report of the current generation.
+    pub fn report(&self) -> GenerationReport {
+        let weights: Vec<f32> = self
+            .population
+            .iter()
+            .filter_map(|individual| individual.minimum_spanning_tree.as_ref())
+            .map(|mst| mst.total_weight)
+            .collect();
+        GenerationReport {
+            generation: self.current_generation,
+            best_weight: weights.iter().copied().fold(f32::INFINITY, f32::min),
+            average_weight: mean(weights.iter().copied()),
+            function_evaluations: self.function_evaluations,
+            diversity: self.diversity(),
+            elapsed: self.runtime(),
+        }
+    }
+
+    /// like [StOBGA::run_until], returning the report of every generation.
+    pub fn run_until_with_history<T: Termination<R>>(
+        &mut self,
+        termination: &mut T,
+    ) -> Vec<GenerationReport> {
+        let mut history = Vec::new();
+        self.run_until_with(termination, |stobga| history.push(stobga.report()));
+        history
+    }
+
+    /// the run of [StOBGA::run] as an iterator, yielding the report of
+    /// each generation.
+    pub fn generations(&mut self) -> Generations<'_, R, impl Termination<R>> {
+        let termination = self.default_termination();
+        self.generations_until(termination)
+    }
+
+    /// like [StOBGA::generations], stopping once `termination` does.
+    pub fn generations_until<T: Termination<R>>(&mut self, termination: T) -> Generations<'_, R, T> {
+        Generations {
+            stobga: self,
+            termination,
+            done: false,
+        }
+    }
+
+    /// the weight of the minimum spanning tree connecting the terminals
+    /// directly, without any Steiner points. It is computed once and then
+    /// kept with the problem.
+    pub fn terminal_mst_weight(&self) -> f32 {
+        *self
+            .problem
+            .terminal_mst_weight
+            .get_or_init(|| self.compute_terminal_mst_weight())
+    }
+
+    /// the tree of an empty chromosome, costed like any individual's tree.
+    fn compute_terminal_mst_weight(&self) -> f32 {
+        self.problem
+            .spanning_tree(
+                &Chromosome::default(),
+                &mut HashMap::new(),
+                &self.config,
+                Some(&self.terminal_distances),
+            )
+            .total_weight
+    }
+
+    /// a summary of the run so far, based on the best individual with a
+    /// built minimum spanning tree.
+    pub fn summary(&self) -> Summary {
+        let best = self
+            .population
+            .iter()
+            .filter(|individual| individual.minimum_spanning_tree.is_some())
+            .min_by(|i1, i2| {
+                let w1 = i1.minimum_spanning_tree.as_ref().unwrap().total_weight;
+                let w2 = i2.minimum_spanning_tree.as_ref().unwrap().total_weight;
+                w1.total_cmp(&w2)
+            })
+            .expect("some individual has a built minimum spanning tree");
+        let best_weight = best.minimum_spanning_tree.as_ref().unwrap().total_weight;
+        let terminal_mst_weight = self.terminal_mst_weight();
+        let solution = Solution::from_individual(best, &self.problem);
+        Summary {
+            best_weight,
+            generations: self.current_generation,
+            best_found_generation: self.best_found_generation,
+            function_evaluations: self.function_evaluations,
+            elapsed: self.runtime(),
+            steiner_count: best.chromosome.steiner_points.len()
+                + best.chromosome.included_corners.iter().count(),
+            feasible: solution.feasible,
+            gap: if terminal_mst_weight > 0.0 {
+                (terminal_mst_weight - best_weight) / terminal_mst_weight
+            } else {
+                0.0
+            },
+            bbox_fill_ratio: solution.bbox_fill_ratio(&self.problem),
+        }
+    }
+}
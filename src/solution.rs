@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use petgraph::visit::EdgeRef;
+
+use crate::{
+    geometry::{euclidean_distance, intersection_length_and_entries, Bounds, Transform},
+    util::{to_graph, to_point},
+    Individual, OPoint, Point, SteinerProblem, StobgaConfig, EPSILON, INF,
+};
+
+/// how many grid steps [Solution::snap_to_grid] looks around a point for a
+/// grid point outside of the solid obstacles.
+const SNAP_SEARCH_RADIUS: usize = 10;
+
+/// the tree of an individual, detached from the algorithm that found it.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Solution {
+    /// the free Steiner points of the tree
+    pub steiner_points: Vec<Point>,
+    /// the indices of the obstacle corners used as Steiner points
+    pub included_corners: Vec<usize>,
+    /// the edges of the minimum spanning tree
+    pub edges: Vec<(Point, Point)>,
+    /// the summed weight of all edges
+    pub total_weight: f32,
+    /// whether the tree avoids all solid obstacles
+    pub feasible: bool,
+}
+
+impl std::fmt::Display for Solution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "weight {}, {} Steiner points, {} obstacle corners, {}",
+            self.total_weight,
+            self.steiner_points.len(),
+            self.included_corners.len(),
+            if self.feasible { "feasible" } else { "infeasible" }
+        )
+    }
+}
+
+impl Solution {
+    /// copies the tree of an individual whose minimum spanning tree is built.
+    pub(crate) fn from_individual(individual: &Individual, problem: &SteinerProblem) -> Self {
+        let mst = individual
+            .minimum_spanning_tree
+            .as_ref()
+            .expect("the individual's minimum spanning tree is built");
+        let graph = &mst.graph;
+        let edges: Vec<(Point, Point)> = graph
+            .edge_references()
+            .map(|edge| (graph[edge.source()], graph[edge.target()]))
+            .collect();
+        Solution {
+            feasible: mst.total_weight < INF
+                && !edges
+                    .iter()
+                    .any(|&(a, b)| problem.segment_crosses_solid_obstacle(a, b)),
+            steiner_points: individual
+                .chromosome
+                .steiner_points
+                .iter()
+                .map(|&p| to_point(p))
+                .collect(),
+            included_corners: individual.chromosome.included_corners.iter().collect(),
+            edges,
+            total_weight: mst.total_weight,
+        }
+    }
+
+    /// the solution with all points mapped through `transform` and the
+    /// weight scaled along, e.g. to map the solution of a
+    /// [normalized](crate::SteinerProblem::normalized) problem back through
+    /// the inverse transform.
+    pub fn transformed(&self, transform: &Transform) -> Solution {
+        Solution {
+            steiner_points: self
+                .steiner_points
+                .iter()
+                .map(|&p| transform.apply(p))
+                .collect(),
+            included_corners: self.included_corners.clone(),
+            edges: self
+                .edges
+                .iter()
+                .map(|&(a, b)| (transform.apply(a), transform.apply(b)))
+                .collect(),
+            total_weight: if self.feasible {
+                self.total_weight * transform.scale
+            } else {
+                self.total_weight
+            },
+            feasible: self.feasible,
+        }
+    }
+
+    /// moves every free Steiner point to the nearest multiple of `step` in
+    /// both coordinates that is not inside a solid obstacle, searching up to
+    /// `SNAP_SEARCH_RADIUS` grid steps away. Points without such a grid
+    /// point nearby stay where they are. Edges, weight and feasibility are
+    /// updated along, with solid obstacles impassable. Panics unless `step`
+    /// is positive.
+    pub fn snap_to_grid(&mut self, step: f32, problem: &SteinerProblem) {
+        assert!(step > 0.0, "grid step {} is not positive", step);
+        let snap = |point: Point| -> Point {
+            let (ix, iy) = ((point.0 / step).round(), (point.1 / step).round());
+            let radius = SNAP_SEARCH_RADIUS as f32;
+            let mut candidates = Vec::new();
+            let mut dx = -radius;
+            while dx <= radius {
+                let mut dy = -radius;
+                while dy <= radius {
+                    candidates.push(((ix + dx) * step, (iy + dy) * step));
+                    dy += 1.0;
+                }
+                dx += 1.0;
+            }
+            candidates.sort_by(|a, b| {
+                euclidean_distance(*a, point).total_cmp(&euclidean_distance(*b, point))
+            });
+            candidates
+                .into_iter()
+                .find(|&candidate| !problem.coordinates_in_solid_obstacle(candidate))
+                .unwrap_or(point)
+        };
+        for index in 0..self.steiner_points.len() {
+            let original = self.steiner_points[index];
+            let snapped = snap(original);
+            self.steiner_points[index] = snapped;
+            for edge in self.edges.iter_mut() {
+                if edge.0 == original {
+                    edge.0 = snapped;
+                }
+                if edge.1 == original {
+                    edge.1 = snapped;
+                }
+            }
+        }
+        let config = StobgaConfig::default();
+        self.total_weight = self
+            .edges
+            .iter()
+            .map(|&(a, b)| problem.edge_cost(to_graph(a), to_graph(b), &config))
+            .sum();
+        self.feasible = self.total_weight < INF
+            && !self
+                .edges
+                .iter()
+                .any(|&(a, b)| problem.segment_crosses_solid_obstacle(a, b));
+    }
+
+    /// the obstacles the tree runs through, by index, each with the
+    /// weighted length of the tree inside it plus its entry costs. For
+    /// solid obstacles this is the length times `INF`.
+    pub fn obstacle_crossings(&self, problem: &SteinerProblem) -> Vec<(usize, f32)> {
+        let mut costs: Vec<Option<f32>> = vec![None; problem.obstacles.len()];
+        for &(a, b) in &self.edges {
+            let length = euclidean_distance(a, b);
+            if length == 0.0 {
+                continue;
+            }
+            let direction = ((b.0 - a.0) / length, (b.1 - a.1) / length);
+            for (index, obstacle) in problem.obstacles.iter().enumerate() {
+                let (inside, entries) = intersection_length_and_entries(
+                    a.0,
+                    a.1,
+                    b.0,
+                    b.1,
+                    &obstacle.points,
+                    &obstacle.bounds,
+                );
+                if inside > 0.0 {
+                    *costs[index].get_or_insert(0.0) += inside * obstacle.weight_along(direction)
+                        + entries as f32 * obstacle.entry_cost;
+                }
+            }
+        }
+        costs
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, cost)| Some((index, cost?)))
+            .collect()
+    }
+
+    /// the degree of every node of the tree.
+    fn degrees(&self) -> HashMap<OPoint, usize> {
+        let mut degrees = HashMap::new();
+        for &(a, b) in &self.edges {
+            *degrees.entry(to_graph(a)).or_insert(0) += 1;
+            *degrees.entry(to_graph(b)).or_insert(0) += 1;
+        }
+        degrees
+    }
+
+    /// the amount of nodes where the tree branches, i.e. of degree three or
+    /// more.
+    pub fn branch_point_count(&self) -> usize {
+        self.degrees().values().filter(|&&degree| degree >= 3).count()
+    }
+
+    /// the amount of nodes of degree one.
+    pub fn leaf_count(&self) -> usize {
+        self.degrees().values().filter(|&&degree| degree == 1).count()
+    }
+
+    /// the length of the longest edge, 0 without edges.
+    pub fn max_edge_length(&self) -> f32 {
+        self.edges
+            .iter()
+            .map(|&(a, b)| euclidean_distance(a, b))
+            .fold(0.0, f32::max)
+    }
+
+    /// the area of the bounding box of all nodes of the tree relative to
+    /// the area of the problem bounds: close to 1 for a tree sprawling over
+    /// the whole instance, small for a compact one. 0 if the problem bounds
+    /// have no area.
+    pub fn bbox_fill_ratio(&self, problem: &SteinerProblem) -> f32 {
+        let nodes = self
+            .edges
+            .iter()
+            .flat_map(|(a, b)| [a, b])
+            .chain(self.steiner_points.iter());
+        let problem_area = problem.bounds().area();
+        if problem_area > 0.0 {
+            Bounds::of_points(nodes).area() / problem_area
+        } else {
+            0.0
+        }
+    }
+
+    /// the structural differences leading from this solution to another one.
+    /// Points closer than `EPSILON` to each other count as the same point.
+    pub fn diff(&self, other: &Solution) -> SolutionDiff {
+        let same_point = |a: Point, b: Point| euclidean_distance(a, b) < EPSILON;
+        let same_edge = |a: (Point, Point), b: (Point, Point)| {
+            (same_point(a.0, b.0) && same_point(a.1, b.1))
+                || (same_point(a.0, b.1) && same_point(a.1, b.0))
+        };
+        let missing_points = |from: &[Point], to: &[Point]| {
+            from.iter()
+                .filter(|&&p| !to.iter().any(|&q| same_point(p, q)))
+                .copied()
+                .collect()
+        };
+        let missing_corners = |from: &[usize], to: &[usize]| {
+            from.iter().filter(|c| !to.contains(c)).copied().collect()
+        };
+        let missing_edges = |from: &[(Point, Point)], to: &[(Point, Point)]| {
+            from.iter()
+                .filter(|&&e| !to.iter().any(|&f| same_edge(e, f)))
+                .copied()
+                .collect()
+        };
+        SolutionDiff {
+            added_steiner_points: missing_points(&other.steiner_points, &self.steiner_points),
+            removed_steiner_points: missing_points(&self.steiner_points, &other.steiner_points),
+            added_corners: missing_corners(&other.included_corners, &self.included_corners),
+            removed_corners: missing_corners(&self.included_corners, &other.included_corners),
+            added_edges: missing_edges(&other.edges, &self.edges),
+            removed_edges: missing_edges(&self.edges, &other.edges),
+            weight_delta: other.total_weight - self.total_weight,
+        }
+    }
+}
+
+/// the changes between two solutions, as computed by [Solution::diff].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolutionDiff {
+    pub added_steiner_points: Vec<Point>,
+    pub removed_steiner_points: Vec<Point>,
+    pub added_corners: Vec<usize>,
+    pub removed_corners: Vec<usize>,
+    pub added_edges: Vec<(Point, Point)>,
+    pub removed_edges: Vec<(Point, Point)>,
+    /// the weight of the other solution minus the weight of this one
+    pub weight_delta: f32,
+}
+
+impl SolutionDiff {
+    /// whether both solutions share the same topology and weight.
+    pub fn is_empty(&self) -> bool {
+        self.added_steiner_points.is_empty()
+            && self.removed_steiner_points.is_empty()
+            && self.added_corners.is_empty()
+            && self.removed_corners.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.weight_delta == 0.0
+    }
+}
+
+/// the outcome of checking a solution against a problem, see
+/// [SteinerProblem::check_solution].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolutionCheck {
+    /// the summed cost of the edges, recomputed with solid obstacles
+    /// impassable
+    pub total_weight: f32,
+    /// the indices of the terminals no edge ends in
+    pub missing_terminals: Vec<usize>,
+    /// whether the edges connect all terminals
+    pub connected: bool,
+    /// whether some edges form a cycle, including repeated edges
+    pub cyclic: bool,
+    /// the edges running through a solid obstacle
+    pub solid_crossings: Vec<(Point, Point)>,
+}
+
+impl SolutionCheck {
+    /// whether the solution is a tree connecting all terminals without
+    /// crossing a solid obstacle.
+    pub fn feasible(&self) -> bool {
+        self.missing_terminals.is_empty()
+            && self.connected
+            && !self.cyclic
+            && self.solid_crossings.is_empty()
+            && self.total_weight < INF
+    }
+}
+
+impl std::fmt::Display for SolutionCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, total weight {}",
+            if self.feasible() { "feasible" } else { "infeasible" },
+            self.total_weight
+        )?;
+        if !self.missing_terminals.is_empty() {
+            write!(f, ", terminals {:?} are not connected", self.missing_terminals)?;
+        } else if !self.connected {
+            write!(f, ", the terminals are not connected")?;
+        }
+        if self.cyclic {
+            write!(f, ", the edges form a cycle")?;
+        }
+        if !self.solid_crossings.is_empty() {
+            write!(f, ", {} edges cross a solid obstacle", self.solid_crossings.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl SteinerProblem {
+    /// checks `solution` using nothing but its edges, e.g. to verify the
+    /// output of another solver. Edge endpoints closer than `EPSILON` to a
+    /// terminal count as that terminal.
+    pub fn check_solution(&self, solution: &Solution) -> SolutionCheck {
+        let node = |p: Point| -> OPoint {
+            let p = self
+                .terminals
+                .iter()
+                .copied()
+                .find(|&terminal| euclidean_distance(p, terminal) < EPSILON)
+                .unwrap_or(p);
+            to_graph(p)
+        };
+        let mut nodes: HashMap<OPoint, usize> = HashMap::new();
+        for &(a, b) in &solution.edges {
+            for p in [node(a), node(b)] {
+                let next = nodes.len();
+                nodes.entry(p).or_insert(next);
+            }
+        }
+        let mut components = petgraph::unionfind::UnionFind::new(nodes.len());
+        let mut cyclic = false;
+        for &(a, b) in &solution.edges {
+            // an edge within a single component closes a cycle
+            cyclic |= !components.union(nodes[&node(a)], nodes[&node(b)]);
+        }
+        // a single terminal needs no edges at all
+        let trivial = self.terminals.len() <= 1;
+        let missing_terminals: Vec<usize> = self
+            .terminals
+            .iter()
+            .positions(|&terminal| !trivial && !nodes.contains_key(&to_graph(terminal)))
+            .collect();
+        let connected = trivial
+            || missing_terminals.is_empty()
+                && self
+                    .terminals
+                    .iter()
+                    .map(|&terminal| components.find(nodes[&to_graph(terminal)]))
+                    .all_equal();
+        let config = StobgaConfig::default();
+        SolutionCheck {
+            total_weight: solution
+                .edges
+                .iter()
+                .map(|&(a, b)| self.edge_cost(to_graph(a), to_graph(b), &config))
+                .sum(),
+            missing_terminals,
+            connected,
+            cyclic,
+            solid_crossings: solution
+                .edges
+                .iter()
+                .filter(|&&(a, b)| self.segment_crosses_solid_obstacle(a, b))
+                .copied()
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! an obstacle free variant of the solver for points in three dimensions.
+//! The 2D solver is untouched by this module; obstacles (prisms or
+//! otherwise) are not supported here.
+
+use nalgebra::Vector3;
+
+use crate::geometry::{self, RADIANS_120_DEGREE};
+use crate::EPSILON;
+
+pub type Point3 = (f32, f32, f32);
+
+/// the result of [`solve`]: the inserted Steiner points and the edges of the
+/// tree spanning them and the terminals.
+#[derive(Debug, Clone)]
+pub struct Tree3 {
+    pub steiner_points: Vec<Point3>,
+    pub edges: Vec<(Point3, Point3)>,
+    pub total_weight: f32,
+}
+
+fn to_vector(p: Point3) -> Vector3<f32> {
+    Vector3::new(p.0, p.1, p.2)
+}
+
+fn to_point3(v: Vector3<f32>) -> Point3 {
+    (v.x, v.y, v.z)
+}
+
+pub fn euclidean_distance(a: Point3, b: Point3) -> f32 {
+    ((a.0 - b.0).powf(2.0) + (a.1 - b.1).powf(2.0) + (a.2 - b.2).powf(2.0)).sqrt()
+}
+
+/// the Fermat point of three points in space. It lies in their plane, so
+/// the triangle is mapped into the plane, solved there with
+/// [`geometry::fermat_point`] and mapped back.
+pub fn fermat_point(a: Point3, b: Point3, c: Point3, epsilon: f32) -> Point3 {
+    let va = to_vector(a);
+    let ab = to_vector(b) - va;
+    let ac = to_vector(c) - va;
+    let normal = ab.cross(&ac);
+    if ab.norm() < epsilon || normal.norm() < epsilon * ab.norm() {
+        // (nearly) collinear: the middle one of the three points
+        return *[a, b, c]
+            .iter()
+            .min_by(|p, q| {
+                let sum = |x: &Point3| {
+                    euclidean_distance(*x, a)
+                        + euclidean_distance(*x, b)
+                        + euclidean_distance(*x, c)
+                };
+                sum(p).total_cmp(&sum(q))
+            })
+            .unwrap();
+    }
+    let e1 = ab.normalize();
+    let e2 = normal.cross(&ab).normalize();
+    let planar = geometry::fermat_point(
+        (0.0, 0.0),
+        (ab.dot(&e1), ab.dot(&e2)),
+        (ac.dot(&e1), ac.dot(&e2)),
+        epsilon,
+    );
+    to_point3(va + e1 * planar.0 + e2 * planar.1)
+}
+
+/// Prim's algorithm on the complete graph of `points`, returning the edges
+/// as index pairs and the total weight.
+fn minimum_spanning_tree(points: &[Point3]) -> (Vec<(usize, usize)>, f32) {
+    let mut in_tree = vec![false; points.len()];
+    let mut distance = vec![f32::INFINITY; points.len()];
+    let mut parent = vec![0; points.len()];
+    let mut edges = Vec::new();
+    let mut total_weight = 0.0;
+    if points.is_empty() {
+        return (edges, total_weight);
+    }
+    distance[0] = 0.0;
+    for _ in 0..points.len() {
+        let next = (0..points.len())
+            .filter(|&i| !in_tree[i])
+            .min_by(|&i, &j| distance[i].total_cmp(&distance[j]))
+            .unwrap();
+        in_tree[next] = true;
+        if next != 0 {
+            edges.push((parent[next], next));
+            total_weight += distance[next];
+        }
+        for i in 0..points.len() {
+            let d = euclidean_distance(points[next], points[i]);
+            if !in_tree[i] && d < distance[i] {
+                distance[i] = d;
+                parent[i] = next;
+            }
+        }
+    }
+    (edges, total_weight)
+}
+
+/// all Fermat points of a node and two of its tree neighbors, between whose
+/// edges the angle is less than 120 degree.
+fn steiner_candidates(points: &[Point3], edges: &[(usize, usize)]) -> Vec<Point3> {
+    let mut candidates = Vec::new();
+    for node in 0..points.len() {
+        let neighbors: Vec<usize> = edges
+            .iter()
+            .filter_map(|&(a, b)| match (a == node, b == node) {
+                (true, _) => Some(b),
+                (_, true) => Some(a),
+                _ => None,
+            })
+            .collect();
+        for (i, &n1) in neighbors.iter().enumerate() {
+            for &n2 in &neighbors[i + 1..] {
+                let v1 = to_vector(points[n1]) - to_vector(points[node]);
+                let v2 = to_vector(points[n2]) - to_vector(points[node]);
+                let angle = (v1.dot(&v2) / (v1.norm() * v2.norm()))
+                    .clamp(-1.0, 1.0)
+                    .acos();
+                if angle < RADIANS_120_DEGREE {
+                    candidates.push(fermat_point(points[node], points[n1], points[n2], EPSILON));
+                }
+            }
+        }
+    }
+    candidates
+}
+
+/// a Steiner tree of `terminals`, found by greedily inserting the Fermat
+/// point that shortens the minimum spanning tree the most, until no
+/// insertion helps anymore.
+pub fn solve(terminals: &[Point3]) -> Tree3 {
+    let mut points = terminals.to_vec();
+    let (mut edges, mut total_weight) = minimum_spanning_tree(&points);
+    loop {
+        let mut best = None;
+        for candidate in steiner_candidates(&points, &edges) {
+            points.push(candidate);
+            let (candidate_edges, weight) = minimum_spanning_tree(&points);
+            points.pop();
+            if weight < total_weight - EPSILON && best.as_ref().is_none_or(|(_, _, w)| weight < *w)
+            {
+                best = Some((candidate, candidate_edges, weight));
+            }
+        }
+        match best {
+            Some((candidate, candidate_edges, weight)) => {
+                points.push(candidate);
+                edges = candidate_edges;
+                total_weight = weight;
+            }
+            None => break,
+        }
+    }
+    Tree3 {
+        steiner_points: points[terminals.len()..].to_vec(),
+        edges: edges.iter().map(|&(a, b)| (points[a], points[b])).collect(),
+        total_weight,
+    }
+}
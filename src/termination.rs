@@ -0,0 +1,87 @@
+//! criteria deciding when [StOBGA::run_until] stops. They are asked once
+//! after every generation.
+
+use rand::Rng;
+
+use crate::util::is_improvement_by_factor;
+use crate::{StOBGA, IMPROVEMENT_FACTOR, INF};
+
+pub trait Termination<R: Rng> {
+    fn should_stop(&mut self, stobga: &StOBGA<R>) -> bool;
+}
+
+/// stops once `duration` generations in a row did not improve significantly
+/// on the best individual.
+pub struct Stagnation {
+    pub duration: usize,
+    previous_best_weight: f32,
+    streak_length: usize,
+}
+
+impl Stagnation {
+    pub fn new(duration: usize) -> Self {
+        Self {
+            duration,
+            previous_best_weight: INF,
+            streak_length: 0,
+        }
+    }
+}
+
+impl<R: Rng> Termination<R> for Stagnation {
+    fn should_stop(&mut self, stobga: &StOBGA<R>) -> bool {
+        let best_weight = match stobga.population[0].minimum_spanning_tree.as_ref() {
+            Some(mst) => mst.total_weight,
+            None => return false,
+        };
+        if is_improvement_by_factor(self.previous_best_weight, best_weight, IMPROVEMENT_FACTOR) {
+            self.previous_best_weight = best_weight;
+            self.streak_length = 0;
+        } else {
+            self.streak_length += 1;
+        }
+        self.streak_length >= self.duration
+    }
+}
+
+/// stops once the given generation is reached.
+pub struct MaxGenerations(pub usize);
+
+impl<R: Rng> Termination<R> for MaxGenerations {
+    fn should_stop(&mut self, stobga: &StOBGA<R>) -> bool {
+        stobga.current_generation >= self.0
+    }
+}
+
+/// stops once the given number of seconds have passed since the solver was
+/// created. Never stops where no clock is available (wasm).
+pub struct TimeLimit(pub f32);
+
+impl<R: Rng> Termination<R> for TimeLimit {
+    fn should_stop(&mut self, stobga: &StOBGA<R>) -> bool {
+        stobga.runtime().is_some_and(|seconds| seconds >= self.0)
+    }
+}
+
+/// stops once the given number of function evaluations has been used up.
+pub struct EvaluationBudget(pub u64);
+
+impl<R: Rng> Termination<R> for EvaluationBudget {
+    fn should_stop(&mut self, stobga: &StOBGA<R>) -> bool {
+        stobga.function_evaluations >= self.0
+    }
+}
+
+/// stops as soon as any of its criteria does. Every criterion is asked each
+/// generation, so stateful ones keep counting.
+pub struct Composite<R: Rng>(pub Vec<Box<dyn Termination<R>>>);
+
+impl<R: Rng> Termination<R> for Composite<R> {
+    fn should_stop(&mut self, stobga: &StOBGA<R>) -> bool {
+        let mut stop = false;
+        for criterion in self.0.iter_mut() {
+            stop |= criterion.should_stop(stobga);
+        }
+        stop
+    }
+}
@@ -1,10 +1,9 @@
 /// a module with utility functions.
-use crate::{OPoint, Point};
-use ordered_float::*;
+use crate::{geometry::{point_in_polygon, Bounds}, OPoint, Point, Pt};
 
-/// turn a Point into an OPoint. *for example to hash it*.
-pub fn to_graph(point: crate::Point) -> OPoint {
-    (OrderedFloat(point.0), OrderedFloat(point.1))
+/// turn a Point (or a [Pt]) into an OPoint. *for example to hash it*.
+pub fn to_graph(point: impl Into<Pt>) -> OPoint {
+    point.into().into()
 }
 
 /// turn an OPoint into a Point. *for example to perform calculations*
@@ -12,8 +11,47 @@ pub fn to_point(point: OPoint) -> Point {
     (*point.0, *point.1)
 }
 
+/// where Steiner points may lie: inside the `polygon`, if any, and
+/// outside of every `keepout` box. The default allows them anywhere.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SteinerRegion<'a> {
+    pub polygon: Option<&'a [Point]>,
+    pub keepout: &'a [Bounds],
+}
+
+/// whether a Steiner point may lie at `point`, see [SteinerRegion].
+pub fn in_region(point: Point, region: SteinerRegion) -> bool {
+    region
+        .polygon
+        .is_none_or(|polygon| point_in_polygon(point.0, point.1, polygon, &Bounds::default()))
+        && !region.keepout.iter().any(|bounds| {
+            bounds.min_x <= point.0
+                && point.0 <= bounds.max_x
+                && bounds.min_y <= point.1
+                && point.1 <= bounds.max_y
+        })
+}
+
+/// whether `new_value` is lower than `current_value` by more than the
+/// relative `factor`: `new_value < current_value - |current_value| * factor`.
+/// With a factor of 0.01% ([IMPROVEMENT_FACTOR](crate::IMPROVEMENT_FACTOR)),
+/// going from 100 to 99.995 is no improvement, to 99.98 is. Every weight
+/// below `INF` improves on `INF`, see [is_first_or_improvement_by_factor]
+/// for counting any first value.
 pub fn is_improvement_by_factor(current_value : f32, new_value : f32, factor : f32) -> bool {
-    new_value < (current_value-current_value*factor)
+    new_value < (current_value-current_value.abs()*factor)
+}
+
+/// like [is_improvement_by_factor] for values to be maximized:
+/// `new_value > current_value + |current_value| * factor`.
+pub fn is_increase_by_factor(current_value : f32, new_value : f32, factor : f32) -> bool {
+    new_value > (current_value+current_value.abs()*factor)
+}
+
+/// like [is_improvement_by_factor], where `None` stands for no value so
+/// far, on which every value improves (even an infeasible one).
+pub fn is_first_or_improvement_by_factor(current_value : Option<f32>, new_value : f32, factor : f32) -> bool {
+    current_value.is_none_or(|current_value| is_improvement_by_factor(current_value, new_value, factor))
 }
 
 pub fn average_from_iterator<I:Iterator<Item=f32> + Clone>(values : I) -> f32 {
@@ -0,0 +1,54 @@
+//! an entry point for running StOBGA in the browser. Enable the `wasm`
+//! feature to export [solve_to_svg] through wasm-bindgen.
+use rand::SeedableRng;
+use serde::Deserialize;
+
+use crate::{Obstacle, Point, StOBGA, SteinerProblem, INF, POPULATION_SIZE};
+
+/// an obstacle as it is written in JSON, e.g.
+/// `{"weight": 5.0, "points": [[0.1, 0.1], [0.2, 0.1], [0.2, 0.2]]}`.
+/// Obstacles without a weight are solid.
+#[derive(Deserialize)]
+struct JsonObstacle {
+    weight: Option<f32>,
+    points: Vec<Point>,
+}
+
+/// solves the instance described by a JSON list of terminals
+/// (`[[x, y], ...]`) and a JSON list of obstacles for the given amount of
+/// generations and returns the best individual drawn as SVG.
+/// Malformed input or less than two terminals yield an empty string.
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
+pub fn solve_to_svg(
+    terminals_json: &str,
+    obstacles_json: &str,
+    seed: u32,
+    generations: u32,
+) -> String {
+    let terminals: Vec<Point> = match serde_json::from_str(terminals_json) {
+        Ok(terminals) => terminals,
+        Err(_) => return String::new(),
+    };
+    let obstacles: Vec<JsonObstacle> = match serde_json::from_str(obstacles_json) {
+        Ok(obstacles) => obstacles,
+        Err(_) => return String::new(),
+    };
+    if terminals.len() < 2 {
+        return String::new();
+    }
+    let obstacles = obstacles
+        .into_iter()
+        .map(|o| Obstacle::new(o.weight.unwrap_or(INF), o.points).compute_bounds())
+        .collect();
+    let rng = rand_pcg::Pcg32::seed_from_u64(seed as u64);
+    let problem = match SteinerProblem::try_new(terminals, obstacles) {
+        Ok(problem) => problem,
+        Err(_) => return String::new(),
+    };
+    let mut stobga = StOBGA::new(rng, problem, POPULATION_SIZE, 1, 50, 50);
+    for _ in 0..generations {
+        stobga.step();
+    }
+    stobga.finalize();
+    stobga.instance_to_svg(0)
+}
@@ -0,0 +1,214 @@
+//! a minimal reader and writer for Well-Known Text, covering the 2D
+//! geometries needed to describe terminals, obstacles and trees.
+
+use crate::{Obstacle, Point, Solution, SteinerProblem, INF};
+
+/// coordinates, possibly nested in parentheses.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    Coordinate(Point),
+    List(Vec<Node>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Geometry {
+    /// the upper case tag, e.g. `POLYGON`
+    pub kind: String,
+    /// `None` for `EMPTY` geometries and geometry collections
+    pub body: Option<Node>,
+    /// the members of a `GEOMETRYCOLLECTION`
+    pub members: Vec<Geometry>,
+}
+
+impl Node {
+    /// all coordinates in the order they appear.
+    fn coordinates(&self) -> Vec<Point> {
+        match self {
+            Node::Coordinate(p) => vec![*p],
+            Node::List(nodes) => nodes.iter().flat_map(Node::coordinates).collect(),
+        }
+    }
+
+    fn children(&self) -> &[Node] {
+        match self {
+            Node::Coordinate(_) => &[],
+            Node::List(nodes) => nodes,
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// consumes `symbol` if it comes next.
+    fn eat(&mut self, symbol: char) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(symbol) {
+            self.position += symbol.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn word(&mut self) -> String {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        self.position += len;
+        rest[..len].to_uppercase()
+    }
+
+    fn number(&mut self) -> Option<f32> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+            .unwrap_or(rest.len());
+        self.position += len;
+        rest[..len].parse().ok()
+    }
+
+    fn node(&mut self) -> Option<Node> {
+        if self.eat('(') {
+            let mut nodes = vec![self.node()?];
+            while self.eat(',') {
+                nodes.push(self.node()?);
+            }
+            self.eat(')').then_some(Node::List(nodes))
+        } else {
+            Some(Node::Coordinate((self.number()?, self.number()?)))
+        }
+    }
+
+    fn geometry(&mut self) -> Option<Geometry> {
+        let kind = self.word();
+        if kind.is_empty() {
+            return None;
+        }
+        let mut geometry = Geometry {
+            kind,
+            body: None,
+            members: Vec::new(),
+        };
+        let checkpoint = self.position;
+        if self.word() == "EMPTY" {
+            return Some(geometry);
+        }
+        self.position = checkpoint;
+        if geometry.kind == "GEOMETRYCOLLECTION" {
+            if !self.eat('(') {
+                return None;
+            }
+            geometry.members.push(self.geometry()?);
+            while self.eat(',') {
+                geometry.members.push(self.geometry()?);
+            }
+            if !self.eat(')') {
+                return None;
+            }
+        } else {
+            geometry.body = Some(self.node()?);
+        }
+        Some(geometry)
+    }
+}
+
+/// all geometries in `text`, which may be separated by whitespace, commas
+/// or semicolons. `None` if the text is malformed.
+pub(crate) fn parse_geometries(text: &str) -> Option<Vec<Geometry>> {
+    let mut parser = Parser { text, position: 0 };
+    let mut geometries = Vec::new();
+    loop {
+        while parser.eat(',') || parser.eat(';') {}
+        parser.skip_whitespace();
+        if parser.rest().is_empty() {
+            return Some(geometries);
+        }
+        geometries.push(parser.geometry()?);
+    }
+}
+
+/// the outer ring of a polygon without the repeated closing point.
+fn outer_ring(polygon: &Node) -> Vec<Point> {
+    let mut ring = polygon
+        .children()
+        .first()
+        .map(Node::coordinates)
+        .unwrap_or_default();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring
+}
+
+impl SteinerProblem {
+    /// reads the terminals from `POINT`/`MULTIPOINT` geometries and solid
+    /// obstacles from `POLYGON`/`MULTIPOLYGON` geometries. Holes of polygons
+    /// are ignored. `None` if either text is malformed or holds non-finite
+    /// coordinates.
+    pub fn from_wkt(points_wkt: &str, polygons_wkt: &str) -> Option<SteinerProblem> {
+        fn flatten(geometries: Vec<Geometry>) -> Vec<Geometry> {
+            geometries
+                .into_iter()
+                .flat_map(|g| {
+                    if g.kind == "GEOMETRYCOLLECTION" {
+                        flatten(g.members)
+                    } else {
+                        vec![g]
+                    }
+                })
+                .collect()
+        }
+        let mut terminals = Vec::new();
+        for geometry in flatten(parse_geometries(points_wkt)?) {
+            match (geometry.kind.as_str(), geometry.body) {
+                ("POINT" | "MULTIPOINT", Some(body)) => terminals.extend(body.coordinates()),
+                ("POINT" | "MULTIPOINT", None) => {}
+                _ => return None,
+            }
+        }
+        let mut obstacles = Vec::new();
+        for geometry in flatten(parse_geometries(polygons_wkt)?) {
+            let polygons = match (geometry.kind.as_str(), &geometry.body) {
+                ("POLYGON", Some(body)) => vec![body.clone()],
+                ("MULTIPOLYGON", Some(body)) => body.children().to_vec(),
+                ("POLYGON" | "MULTIPOLYGON", None) => vec![],
+                _ => return None,
+            };
+            for polygon in polygons {
+                obstacles.push(Obstacle::new(INF, outer_ring(&polygon)).compute_bounds());
+            }
+        }
+        SteinerProblem::try_new(terminals, obstacles).ok()
+    }
+}
+
+impl Solution {
+    /// the edges of the tree as a `GEOMETRYCOLLECTION` of `LINESTRING`s.
+    pub fn to_wkt(&self) -> String {
+        if self.edges.is_empty() {
+            return "GEOMETRYCOLLECTION EMPTY".to_string();
+        }
+        let lines = self
+            .edges
+            .iter()
+            .map(|(a, b)| format!("LINESTRING ({} {}, {} {})", a.0, a.1, b.0, b.1))
+            .collect::<Vec<_>>();
+        format!("GEOMETRYCOLLECTION ({})", lines.join(", "))
+    }
+}
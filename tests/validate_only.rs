@@ -0,0 +1,55 @@
+//! runs the binary with `--validate-only` and checks its exit codes.
+use std::path::PathBuf;
+use std::process::Command;
+
+const TERMINALS: &str = "x,y
+0,0
+4,0
+2,3
+";
+
+const OBSTACLES: &str = "max
+1,-1
+2,-1
+2,1
+1,1";
+
+/// writes the instance and `solution` to a fresh directory and validates the
+/// solution, returning the exit code.
+fn validate(name: &str, solution: &str) -> Option<i32> {
+    let dir: PathBuf = std::env::temp_dir().join(format!("stobga-validate-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let files = [("terminals.csv", TERMINALS), ("obstacles.csv", OBSTACLES), ("solution.json", solution)];
+    for (file, content) in files {
+        std::fs::write(dir.join(file), content).unwrap();
+    }
+    let status = Command::new(env!("CARGO_BIN_EXE_stobga"))
+        .arg("--validate-only")
+        .args(files.map(|(file, _)| dir.join(file)))
+        .output()
+        .unwrap()
+        .status;
+    std::fs::remove_dir_all(&dir).unwrap();
+    status.code()
+}
+
+#[test]
+fn exit_codes_of_validate_only() {
+    let good = r#"{
+        "steiner_points": [],
+        "included_corners": [],
+        "edges": [[[0.0, 0.0], [2.0, 3.0]], [[2.0, 3.0], [4.0, 0.0]]],
+        "total_weight": 7.2,
+        "feasible": true
+    }"#;
+    assert_eq!(validate("good", good), Some(0));
+    // through the solid obstacle, leaving out the top terminal
+    let broken = r#"{
+        "steiner_points": [],
+        "included_corners": [],
+        "edges": [[[0.0, 0.0], [4.0, 0.0]]],
+        "total_weight": 4.0,
+        "feasible": true
+    }"#;
+    assert_eq!(validate("broken", broken), Some(1));
+}